@@ -25,6 +25,7 @@ pub enum Stmt {
     },
 
     If{branches: Vec<Branch>, else_stmts: Option<Block>},
+    Match{loc: Location, expr: Expr, arms: Vec<MatchArm>},
 
     While{cond: Expr, stmts: Block},
     For{lhs: Expr, iter: Expr, stmts: Block},
@@ -38,6 +39,17 @@ pub enum Stmt {
         stmts: Block,
     },
     Return{loc: Location, expr: Expr},
+
+    Enum{name: (String, Location), variants: Vec<EnumVariant>},
+}
+
+// `EnumVariant` is a variant of an `enum` declaration. `fields` names the
+// values of the payload of the variant, which is empty if the variant doesn't
+// have a payload.
+#[derive(Clone, Debug)]
+pub struct EnumVariant {
+    pub name: (String, Location),
+    pub fields: Vec<String>,
 }
 
 #[derive(Clone,Debug)]
@@ -46,6 +58,15 @@ pub struct Branch {
     pub stmts: Block,
 }
 
+// `MatchArm` is an arm of a `match` statement. `stmts` are evaluated if the
+// matched value fits `pattern` and `guard`, if present, evaluates to `true`.
+#[derive(Clone, Debug)]
+pub struct MatchArm {
+    pub pattern: Expr,
+    pub guard: Option<Expr>,
+    pub stmts: Block,
+}
+
 #[derive(Clone,Debug)]
 pub enum DeclarationType {
     Const,
@@ -62,10 +83,11 @@ pub enum RawExpr {
 
     Bool{b: bool},
     Int{n: i64},
+    Float{n: f64},
     // `interpolation_slots` is `None` iff the string isn't interpolated,
-    // otherwise it contains start/end indices of substrings to be evaluated
-    // during interpolation.
-    Str{s: String, interpolation_slots: Option<Vec<(usize, usize)>>},
+    // otherwise it contains the slots of `s` to be evaluated during
+    // interpolation.
+    Str{s: String, interpolation_slots: Option<Vec<InterpolationSlot>>},
 
     Var{name: String},
 
@@ -98,11 +120,23 @@ pub enum RawExpr {
     Call{func: Box<Expr>, args: Vec<ListItem>},
 
     CatchAsBool{expr: Box<Expr>},
+
+    // `args` are evaluated to form the argument vector of an external
+    // command; the first argument is the name of the program to run.
+    Command{args: Vec<ListItem>},
+    // `stages` contains at least two expressions, each of which is expected
+    // to be a `Command`.
+    Pipeline{stages: Vec<Expr>},
+
+    // `path` is resolved relative to the directory of the script that
+    // contains the import.
+    Import{path: String},
 }
 
 #[derive(Clone, Debug)]
 pub enum UnaryOp {
     Not,
+    BitNot,
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -112,6 +146,13 @@ pub enum BinaryOp {
     Mul,
     Div,
     Mod,
+    Pow,
+
+    BitAnd,
+    BitOr,
+    BitXor,
+    Shl,
+    Shr,
 
     And,
     Or,
@@ -127,6 +168,32 @@ pub enum BinaryOp {
     RefNe,
 }
 
+// `InterpolationSlot` is a slot of an interpolated string. `start` and `end`
+// are the byte indices of the slot, including its delimiters, in the string.
+#[derive(Clone, Debug)]
+pub struct InterpolationSlot {
+    pub start: usize,
+    pub end: usize,
+    pub expr: Expr,
+    pub format_spec: Option<FormatSpec>,
+}
+
+// `FormatSpec` defines how the value of an interpolation slot is rendered.
+#[derive(Clone, Debug, PartialEq)]
+pub enum FormatSpec {
+    Json,
+    // `zero` is `true` if ints should be padded with zeros after their sign,
+    // in which case `align` is `None`.
+    Pad{fill: char, align: Option<Align>, zero: bool, width: usize},
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Align {
+    Left,
+    Right,
+    Center,
+}
+
 #[derive(Clone,Debug)]
 pub struct ListItem {
     pub expr: Expr,
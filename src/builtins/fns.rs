@@ -2,20 +2,33 @@
 // Use of this source code is governed by an MIT
 // licence that can be found in the LICENCE file.
 
+use std::ffi::OsString;
+use std::os::unix::ffi::OsStringExt;
+use std::path::Path;
+use std::path::PathBuf;
+
 use snafu::ResultExt;
 
+use crate::eval;
 use crate::eval::error::AssertArgsFailed;
 use crate::eval::error::AssertNoThisFailed;
 use crate::eval::error::Error;
 use crate::eval::error::Result;
+use crate::eval::EvaluationContext;
 use crate::eval::value;
 use crate::eval::value::Func;
 use crate::eval::value::SourcedValue;
+use crate::eval::value::Str;
 use crate::eval::value::Value;
 use crate::lock_deref;
 
 #[allow(clippy::needless_pass_by_value)]
-pub fn print(this: Option<SourcedValue>, args: Vec<SourcedValue>)
+pub fn print(
+    _context: &EvaluationContext,
+    _loc: (usize, usize),
+    this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
     -> Result<SourcedValue>
 {
     assert_args("print", 1, &args)
@@ -31,7 +44,127 @@ pub fn print(this: Option<SourcedValue>, args: Vec<SourcedValue>)
     Ok(value::new_null())
 }
 
-fn render(v: &SourcedValue) -> Result<String> {
+// `exit` stops evaluation of the script, and exits the current process with
+// the given exit code.
+#[allow(clippy::needless_pass_by_value)]
+pub fn exit(
+    _context: &EvaluationContext,
+    _loc: (usize, usize),
+    this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    assert_args("exit", 1, &args)
+        .context(AssertArgsFailed)?;
+
+    assert_no_this(this.as_ref())
+        .context(AssertNoThisFailed)?;
+
+    let code =
+        match args[0].v {
+            Value::Int(n) if (0..=255).contains(&n) => n,
+            Value::Int(n) => return Err(Error::BuiltinFuncErr{msg: format!(
+                "exit code must be between 0 and 255 (got {n})",
+            )}),
+            _ => return Err(Error::IncorrectType{
+                descr: "`code`".to_string(),
+                exp_type: "int".to_string(),
+                value: args[0].v.clone(),
+            }),
+        };
+
+    let code = i32::try_from(code)
+        .expect("`code` should fit in an `i32`");
+
+    Err(Error::Exit{code})
+}
+
+// `cd` changes the working directory of the script, which is also the
+// directory that commands are run in.
+#[allow(clippy::needless_pass_by_value)]
+pub fn cd(
+    context: &EvaluationContext,
+    _loc: (usize, usize),
+    this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    assert_args("cd", 1, &args)
+        .context(AssertArgsFailed)?;
+
+    assert_no_this(this.as_ref())
+        .context(AssertNoThisFailed)?;
+
+    let path = assert_path("path", &args[0])?;
+
+    change_dir(context, &path)?;
+
+    Ok(value::new_null())
+}
+
+// `with_dir` calls `f` with the working directory of the script set to
+// `path`, and then restores the previous working directory, even if `f`
+// returns an error.
+#[allow(clippy::needless_pass_by_value)]
+pub fn with_dir(
+    context: &EvaluationContext,
+    loc: (usize, usize),
+    this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    assert_args("with_dir", 2, &args)
+        .context(AssertArgsFailed)?;
+
+    assert_no_this(this.as_ref())
+        .context(AssertNoThisFailed)?;
+
+    let path = assert_path("path", &args[0])?;
+    let f = assert_func("f", &args[1])?;
+
+    let prev_dir = context.cwd();
+    change_dir(context, &path)?;
+
+    let result = call(context, loc, &f, vec![]);
+
+    context.set_cwd(prev_dir);
+
+    result
+}
+
+fn change_dir(context: &EvaluationContext, path: &Path) -> Result<()> {
+    let dir =
+        match context.resolve_path(path).canonicalize() {
+            Ok(dir) => dir,
+            Err(e) => return Err(Error::Runtime{msg: format!(
+                "couldn't change directory to '{}': {e}",
+                path.display(),
+            )}),
+        };
+
+    if !dir.is_dir() {
+        return Err(Error::Runtime{msg: format!(
+            "couldn't change directory to '{}': not a directory",
+            path.display(),
+        )});
+    }
+
+    context.set_cwd(dir);
+
+    Ok(())
+}
+
+// `render_float` returns the rendering of `n`, which always contains a `.` or
+// an exponent (unless `n` isn't finite), so that floats can be distinguished
+// from ints when they're printed.
+pub fn render_float(n: f64) -> String {
+    format!("{n:?}")
+}
+
+pub fn render(v: &SourcedValue) -> Result<String> {
     let mut s = String::new();
 
     match v.v.clone() {
@@ -47,6 +180,10 @@ fn render(v: &SourcedValue) -> Result<String> {
             s += &format!("{n}");
         },
 
+        Value::Float(n) => {
+            s += &render_float(n);
+        },
+
         Value::Str(raw_str) => {
             let rendered_str =
                 match String::from_utf8(raw_str) {
@@ -98,11 +235,47 @@ fn render(v: &SourcedValue) -> Result<String> {
 
             s += &format!("<function '{name:?}'>");
         },
+
+        Value::Enum(typ) => {
+            s += &format!("<enum '{}'>", typ.name);
+        },
+
+        Value::EnumConstructor{typ, variant} => {
+            let name = value::variant_name(&typ, variant);
+
+            s += &format!("<enum constructor '{name}'>");
+        },
+
+        Value::Variant{typ, variant, payload} => {
+            s += &value::variant_name(&typ, variant);
+            if !typ.variants[variant].fields.is_empty() {
+                let items: Vec<String> = payload.iter()
+                    .map(render)
+                    .collect::<Result<_>>()?;
+
+                s += &format!("({})", items.join(", "));
+            }
+        },
     }
 
     Ok(s.to_string())
 }
 
+// `call` calls `f`, which can be an Ash function or a built-in function, with
+// `args`, where `loc` is the location of the call to the built-in function
+// that's calling `f`. Errors that occur within `f` are reported as occurring
+// within that built-in function, at `loc`.
+pub fn call(
+    context: &EvaluationContext,
+    loc: (usize, usize),
+    f: &SourcedValue,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    eval::call_func(context, f.clone(), args, loc)
+}
+
 // `assert_args` asserts that the correct number of arguments were passed for
 // built-in functions.
 pub fn assert_args(fn_name: &str, exp_args: usize, args: &[SourcedValue])
@@ -125,6 +298,29 @@ pub fn assert_args(fn_name: &str, exp_args: usize, args: &[SourcedValue])
     Ok(())
 }
 
+// `assert_args_between` asserts that between `min_args` and `max_args`
+// arguments (inclusive) were passed for built-in functions that take optional
+// arguments.
+pub fn assert_args_between(
+    fn_name: &str,
+    min_args: usize,
+    max_args: usize,
+    args: &[SourcedValue],
+)
+    -> Result<()>
+{
+    let args_len = args.len();
+
+    if args_len < min_args || args_len > max_args {
+        return Err(Error::BuiltinFuncErr{msg: format!(
+            "`{fn_name}` takes between {min_args} and {max_args} arguments \
+             (got {args_len})",
+        )})
+    }
+
+    Ok(())
+}
+
 pub fn assert_no_this(this: Option<&SourcedValue>) -> Result<()> {
     if this.is_none() {
         Ok(())
@@ -156,3 +352,100 @@ pub fn assert_str(val_name: &str, v: &SourcedValue) -> Result<String> {
         Err(Error::Dev{msg: m.to_string()})
     }
 }
+
+pub fn assert_bool(val_name: &str, v: &SourcedValue) -> Result<bool> {
+    if let Value::Bool(b) = &v.v {
+        Ok(*b)
+    } else {
+        Err(Error::IncorrectType{
+            descr: format!("`{val_name}`"),
+            exp_type: "bool".to_string(),
+            value: v.v.clone(),
+        })
+    }
+}
+
+pub fn assert_int(val_name: &str, v: &SourcedValue) -> Result<i64> {
+    if let Value::Int(n) = &v.v {
+        Ok(*n)
+    } else {
+        Err(Error::IncorrectType{
+            descr: format!("`{val_name}`"),
+            exp_type: "int".to_string(),
+            value: v.v.clone(),
+        })
+    }
+}
+
+pub fn assert_float(val_name: &str, v: &SourcedValue) -> Result<f64> {
+    if let Value::Float(n) = &v.v {
+        Ok(*n)
+    } else {
+        Err(Error::IncorrectType{
+            descr: format!("`{val_name}`"),
+            exp_type: "float".to_string(),
+            value: v.v.clone(),
+        })
+    }
+}
+
+pub fn assert_func(val_name: &str, v: &SourcedValue)
+    -> Result<SourcedValue>
+{
+    match &v.v {
+        Value::BuiltinFunc{..} | Value::Func(_) => Ok(v.clone()),
+        value => Err(Error::IncorrectType{
+            descr: format!("`{val_name}`"),
+            exp_type: "func".to_string(),
+            value: value.clone(),
+        }),
+    }
+}
+
+// `assert_raw_str` is like `assert_str`, but doesn't require the string to be
+// valid UTF-8.
+pub fn assert_raw_str(val_name: &str, v: &SourcedValue) -> Result<Str> {
+    if let Value::Str(s) = &v.v {
+        Ok(s.clone())
+    } else {
+        Err(Error::IncorrectType{
+            descr: format!("`{val_name}`"),
+            exp_type: "string".to_string(),
+            value: v.v.clone(),
+        })
+    }
+}
+
+pub fn assert_path(val_name: &str, v: &SourcedValue) -> Result<PathBuf> {
+    let s = assert_raw_str(val_name, v)?;
+
+    Ok(PathBuf::from(OsString::from_vec(s)))
+}
+
+// `assert_bool_opt` returns the value of the `opt_name` option of the `opts`
+// object passed to `fn_name`, which must be the only option that `opts` has.
+pub fn assert_bool_opt(fn_name: &str, opt_name: &str, opts: &SourcedValue)
+    -> Result<bool>
+{
+    let props =
+        match &opts.v {
+            Value::Object{props, ..} => props,
+            value => return Err(Error::IncorrectType{
+                descr: "`opts`".to_string(),
+                exp_type: "object".to_string(),
+                value: value.clone(),
+            }),
+        };
+
+    let mut opt = false;
+    for (name, v) in &lock_deref!(props) {
+        if name != opt_name {
+            return Err(Error::BuiltinFuncErr{msg: format!(
+                "`{fn_name}` doesn't support the '{name}' option",
+            )});
+        }
+        opt = assert_bool(name, v)?;
+    }
+
+    Ok(opt)
+}
@@ -3,4 +3,5 @@
 // licence that can be found in the LICENCE file.
 
 pub mod fns;
+pub mod stdlib;
 pub mod type_functions;
@@ -0,0 +1,405 @@
+// Copyright 2026 Sean Kelleher. All rights reserved.
+// Use of this source code is governed by an MIT
+// licence that can be found in the LICENCE file.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::fs::Metadata;
+use std::fs::OpenOptions;
+use std::io;
+use std::io::Write;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::fs::PermissionsExt;
+use std::time::UNIX_EPOCH;
+
+use snafu::ResultExt;
+
+use super::new_io_err;
+use super::new_io_transfer_err;
+use crate::builtins::fns;
+use crate::eval::error::AssertArgsFailed;
+use crate::eval::error::Error;
+use crate::eval::error::Result;
+use crate::eval::EvaluationContext;
+use crate::eval::glob;
+use crate::eval::scope::Mutability;
+use crate::eval::value;
+use crate::eval::value::SourcedValue;
+
+// `read` returns the contents of the file at `path` as a string.
+#[allow(clippy::needless_pass_by_value)]
+pub fn read(
+    context: &EvaluationContext,
+    _loc: (usize, usize),
+    _this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("read", 1, &args)
+        .context(AssertArgsFailed)?;
+
+    let path = fns::assert_path("path", &args[0])?;
+    let abs_path = context.resolve_path(&path);
+
+    let contents = fs::read(&abs_path)
+        .map_err(|e| new_io_err("read", &path, &e))?;
+
+    Ok(value::new_str(contents))
+}
+
+// `read_bytes` returns the contents of the file at `path` as a list of ints,
+// one for each byte of the file.
+#[allow(clippy::needless_pass_by_value)]
+pub fn read_bytes(
+    context: &EvaluationContext,
+    _loc: (usize, usize),
+    _this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("read_bytes", 1, &args)
+        .context(AssertArgsFailed)?;
+
+    let path = fns::assert_path("path", &args[0])?;
+    let abs_path = context.resolve_path(&path);
+
+    let contents = fs::read(&abs_path)
+        .map_err(|e| new_io_err("read", &path, &e))?;
+
+    let bytes = contents.into_iter()
+        .map(|b| value::new_int(i64::from(b)))
+        .collect();
+
+    Ok(value::new_list(bytes, &Mutability::Const))
+}
+
+// `write` replaces the contents of the file at `path` with `content`, creating
+// the file if it doesn't exist.
+#[allow(clippy::needless_pass_by_value)]
+pub fn write(
+    context: &EvaluationContext,
+    _loc: (usize, usize),
+    _this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("write", 2, &args)
+        .context(AssertArgsFailed)?;
+
+    let path = fns::assert_path("path", &args[0])?;
+    let abs_path = context.resolve_path(&path);
+    let content = fns::assert_raw_str("content", &args[1])?;
+
+    fs::write(&abs_path, content)
+        .map_err(|e| new_io_err("write", &path, &e))?;
+
+    Ok(value::new_null())
+}
+
+// `append` adds `content` to the end of the file at `path`, creating the file
+// if it doesn't exist.
+#[allow(clippy::needless_pass_by_value)]
+pub fn append(
+    context: &EvaluationContext,
+    _loc: (usize, usize),
+    _this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("append", 2, &args)
+        .context(AssertArgsFailed)?;
+
+    let path = fns::assert_path("path", &args[0])?;
+    let abs_path = context.resolve_path(&path);
+    let content = fns::assert_raw_str("content", &args[1])?;
+
+    OpenOptions::new()
+        .append(true)
+        .create(true)
+        .open(&abs_path)
+        .and_then(|mut f| f.write_all(&content))
+        .map_err(|e| new_io_err("append to", &path, &e))?;
+
+    Ok(value::new_null())
+}
+
+// `exists` returns whether `path` refers to an existing file or directory.
+// Symbolic links are followed, so a broken link doesn't exist.
+#[allow(clippy::needless_pass_by_value)]
+pub fn exists(
+    context: &EvaluationContext,
+    _loc: (usize, usize),
+    _this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("exists", 1, &args)
+        .context(AssertArgsFailed)?;
+
+    let path = fns::assert_path("path", &args[0])?;
+    let abs_path = context.resolve_path(&path);
+
+    let exists = abs_path.try_exists()
+        .map_err(|e| new_io_err("check", &path, &e))?;
+
+    Ok(value::new_bool(exists))
+}
+
+// `is_dir` returns whether `path` refers to a directory, following symbolic
+// links.
+#[allow(clippy::needless_pass_by_value)]
+pub fn is_dir(
+    context: &EvaluationContext,
+    _loc: (usize, usize),
+    _this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("is_dir", 1, &args)
+        .context(AssertArgsFailed)?;
+
+    let path = fns::assert_path("path", &args[0])?;
+    let abs_path = context.resolve_path(&path);
+
+    Ok(value::new_bool(abs_path.is_dir()))
+}
+
+// `list_dir` returns the names of the entries of the directory at `path`,
+// in sorted order.
+#[allow(clippy::needless_pass_by_value)]
+pub fn list_dir(
+    context: &EvaluationContext,
+    _loc: (usize, usize),
+    _this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("list_dir", 1, &args)
+        .context(AssertArgsFailed)?;
+
+    let path = fns::assert_path("path", &args[0])?;
+    let abs_path = context.resolve_path(&path);
+
+    let mut names = fs::read_dir(&abs_path)
+        .and_then(|entries| {
+            entries
+                .map(|entry| Ok(entry?.file_name().as_bytes().to_vec()))
+                .collect::<io::Result<Vec<_>>>()
+        })
+        .map_err(|e| new_io_err("list", &path, &e))?;
+
+    names.sort();
+
+    let names = names.into_iter()
+        .map(value::new_str)
+        .collect();
+
+    Ok(value::new_list(names, &Mutability::Const))
+}
+
+// `stat` returns an object describing the file at `path`. Symbolic links are
+// followed, but `is_symlink` reports whether `path` itself is a link.
+#[allow(clippy::needless_pass_by_value)]
+pub fn stat(
+    context: &EvaluationContext,
+    _loc: (usize, usize),
+    _this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("stat", 1, &args)
+        .context(AssertArgsFailed)?;
+
+    let path = fns::assert_path("path", &args[0])?;
+    let abs_path = context.resolve_path(&path);
+
+    let metadata = fs::metadata(&abs_path)
+        .map_err(|e| new_io_err("stat", &path, &e))?;
+    let is_symlink = abs_path.is_symlink();
+
+    Ok(new_stat_object(&metadata, is_symlink))
+}
+
+fn new_stat_object(metadata: &Metadata, is_symlink: bool) -> SourcedValue {
+    let size = i64::try_from(metadata.len())
+        .unwrap_or(i64::MAX);
+
+    let modified =
+        match metadata.modified().map(|t| t.duration_since(UNIX_EPOCH)) {
+            Ok(Ok(d)) => value::new_int(
+                i64::try_from(d.as_secs()).unwrap_or(i64::MAX),
+            ),
+            _ => value::new_null(),
+        };
+
+    let mode = metadata.permissions().mode() & 0o7777;
+
+    let props = BTreeMap::from([
+        ("size".to_string(), value::new_int(size)),
+        ("is_dir".to_string(), value::new_bool(metadata.is_dir())),
+        ("is_file".to_string(), value::new_bool(metadata.is_file())),
+        ("is_symlink".to_string(), value::new_bool(is_symlink)),
+        ("mode".to_string(), value::new_int(i64::from(mode))),
+        ("modified".to_string(), modified),
+    ]);
+
+    value::new_object(props, &Mutability::Const)
+}
+
+// `mkdir` creates the directory at `path`, along with any missing parent
+// directories. It isn't an error for the directory to already exist.
+#[allow(clippy::needless_pass_by_value)]
+pub fn mkdir(
+    context: &EvaluationContext,
+    _loc: (usize, usize),
+    _this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("mkdir", 1, &args)
+        .context(AssertArgsFailed)?;
+
+    let path = fns::assert_path("path", &args[0])?;
+    let abs_path = context.resolve_path(&path);
+
+    fs::create_dir_all(&abs_path)
+        .map_err(|e| new_io_err("create", &path, &e))?;
+
+    Ok(value::new_null())
+}
+
+// `remove` removes the file or empty directory at `path`. Non-empty
+// directories are only removed if the `recursive` option is set.
+#[allow(clippy::needless_pass_by_value)]
+pub fn remove(
+    context: &EvaluationContext,
+    _loc: (usize, usize),
+    _this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args_between("remove", 1, 2, &args)
+        .context(AssertArgsFailed)?;
+
+    let path = fns::assert_path("path", &args[0])?;
+    let abs_path = context.resolve_path(&path);
+
+    let mut recursive = false;
+    if let Some(opts) = args.get(1) {
+        recursive = fns::assert_bool_opt("remove", "recursive", opts)?;
+    }
+
+    let metadata = fs::symlink_metadata(&abs_path)
+        .map_err(|e| new_io_err("remove", &path, &e))?;
+
+    let result =
+        if !metadata.is_dir() {
+            fs::remove_file(&abs_path)
+        } else if recursive {
+            fs::remove_dir_all(&abs_path)
+        } else {
+            fs::remove_dir(&abs_path)
+        };
+
+    result
+        .map_err(|e| new_io_err("remove", &path, &e))?;
+
+    Ok(value::new_null())
+}
+
+// `copy` copies the contents and permissions of the file at `src` to `dst`,
+// replacing `dst` if it exists.
+#[allow(clippy::needless_pass_by_value)]
+pub fn copy(
+    context: &EvaluationContext,
+    _loc: (usize, usize),
+    _this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("copy", 2, &args)
+        .context(AssertArgsFailed)?;
+
+    let src = fns::assert_path("src", &args[0])?;
+    let dst = fns::assert_path("dst", &args[1])?;
+    let abs_src = context.resolve_path(&src);
+    let abs_dst = context.resolve_path(&dst);
+
+    fs::copy(&abs_src, &abs_dst)
+        .map_err(|e| new_io_transfer_err("copy", &src, &dst, &e))?;
+
+    Ok(value::new_null())
+}
+
+// `rename` moves the file or directory at `src` to `dst`, replacing `dst` if
+// it's a file.
+#[allow(clippy::needless_pass_by_value)]
+pub fn rename(
+    context: &EvaluationContext,
+    _loc: (usize, usize),
+    _this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("rename", 2, &args)
+        .context(AssertArgsFailed)?;
+
+    let src = fns::assert_path("src", &args[0])?;
+    let dst = fns::assert_path("dst", &args[1])?;
+    let abs_src = context.resolve_path(&src);
+    let abs_dst = context.resolve_path(&dst);
+
+    fs::rename(&abs_src, &abs_dst)
+        .map_err(|e| new_io_transfer_err("rename", &src, &dst, &e))?;
+
+    Ok(value::new_null())
+}
+
+// `glob` returns the paths that match `pattern`, in sorted order. If the
+// `must_match` option is set then it's an error for no paths to match.
+#[allow(clippy::needless_pass_by_value)]
+pub fn glob(
+    context: &EvaluationContext,
+    _loc: (usize, usize),
+    _this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args_between("glob", 1, 2, &args)
+        .context(AssertArgsFailed)?;
+
+    let pattern = fns::assert_raw_str("pattern", &args[0])?;
+
+    let mut must_match = false;
+    if let Some(opts) = args.get(1) {
+        must_match = fns::assert_bool_opt("glob", "must_match", opts)?;
+    }
+
+    let paths = glob::glob(&context.cwd(), &pattern);
+
+    if must_match && paths.is_empty() {
+        return Err(Error::Runtime{msg: format!(
+            "no paths match '{}'",
+            String::from_utf8_lossy(&pattern),
+        )});
+    }
+
+    let paths = paths.into_iter()
+        .map(value::new_str)
+        .collect();
+
+    Ok(value::new_list(paths, &Mutability::Const))
+}
@@ -0,0 +1,73 @@
+// Copyright 2026 Sean Kelleher. All rights reserved.
+// Use of this source code is governed by an MIT
+// licence that can be found in the LICENCE file.
+
+use snafu::ResultExt;
+
+use crate::builtins::fns;
+use crate::eval::error::AssertArgsFailed;
+use crate::eval::error::Error;
+use crate::eval::error::Result;
+use crate::eval::json;
+use crate::eval::scope::Mutability;
+use crate::eval::EvaluationContext;
+use crate::eval::value;
+use crate::eval::value::SourcedValue;
+
+// `parse` returns the value that the JSON document `s` represents. The lists
+// and objects in the result are immutable, unless the `mutable` option is set.
+#[allow(clippy::needless_pass_by_value)]
+pub fn parse(
+    _context: &EvaluationContext,
+    _loc: (usize, usize),
+    _this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args_between("parse", 1, 2, &args)
+        .context(AssertArgsFailed)?;
+
+    let s = fns::assert_raw_str("s", &args[0])?;
+
+    let mut mutable = false;
+    if let Some(opts) = args.get(1) {
+        mutable = fns::assert_bool_opt("parse", "mutable", opts)?;
+    }
+
+    let src =
+        match String::from_utf8(s) {
+            Ok(src) => src,
+            Err(e) => return Err(Error::Runtime{msg: format!(
+                "couldn't parse JSON, because it isn't valid UTF-8: {e}",
+            )}),
+        };
+
+    let mutability = if mutable { Mutability::Var } else { Mutability::Const };
+
+    json::parse(&src, &mutability)
+}
+
+// `stringify` returns the JSON encoding of `v`. The encoding is written over
+// multiple, indented lines if the `pretty` option is set.
+#[allow(clippy::needless_pass_by_value)]
+pub fn stringify(
+    _context: &EvaluationContext,
+    _loc: (usize, usize),
+    _this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args_between("stringify", 1, 2, &args)
+        .context(AssertArgsFailed)?;
+
+    let mut pretty = false;
+    if let Some(opts) = args.get(1) {
+        pretty = fns::assert_bool_opt("stringify", "pretty", opts)?;
+    }
+
+    let s = json::stringify(&args[0].v, pretty)?;
+
+    Ok(value::new_str_from_string(s))
+}
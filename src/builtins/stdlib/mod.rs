@@ -0,0 +1,110 @@
+// Copyright 2026 Sean Kelleher. All rights reserved.
+// Use of this source code is governed by an MIT
+// licence that can be found in the LICENCE file.
+
+use std::io;
+use std::path::Path;
+
+use super::type_functions;
+use crate::eval::error::Error;
+use crate::eval::scope::Mutability;
+use crate::eval::value;
+use crate::eval::value::BuiltinFunc;
+use crate::eval::value::ObjectRef;
+use crate::eval::value::SourcedValue;
+
+mod fs;
+mod json;
+mod path;
+mod proc;
+mod strs;
+
+// `stdlib` returns the modules of the standard library, which are accessed
+// through the global `std` object.
+//
+// Note that the functions of a module are called with the module object as
+// `this`, because they're accessed as properties of the module.
+pub fn stdlib() -> ObjectRef {
+    type_functions::new_func_map(vec![
+        new_module("fs", &[
+            ("append", fs::append),
+            ("copy", fs::copy),
+            ("exists", fs::exists),
+            ("glob", fs::glob),
+            ("is_dir", fs::is_dir),
+            ("list_dir", fs::list_dir),
+            ("mkdir", fs::mkdir),
+            ("read", fs::read),
+            ("read_bytes", fs::read_bytes),
+            ("remove", fs::remove),
+            ("rename", fs::rename),
+            ("stat", fs::stat),
+            ("write", fs::write),
+        ]),
+        new_module("json", &[
+            ("parse", json::parse),
+            ("stringify", json::stringify),
+        ]),
+        new_module("path", &[
+            ("absolute", path::absolute),
+            ("basename", path::basename),
+            ("canonicalize", path::canonicalize),
+            ("dirname", path::dirname),
+            ("expand_user", path::expand_user),
+            ("extension", path::extension),
+            ("join", path::join),
+            ("relative_to", path::relative_to),
+            ("stem", path::stem),
+        ]),
+        new_module("proc", &[
+            ("pipefail", proc::pipefail),
+            ("run", proc::run),
+        ]),
+        new_module("str", &[
+            ("parse_int", strs::parse_int),
+        ]),
+    ])
+}
+
+// `new_module` returns the module `name`, paired with its name, where the
+// module contains the functions in `funcs`, and each function is named
+// `std.<name>.<func_name>`.
+fn new_module(name: &str, funcs: &[(&str, BuiltinFunc)])
+    -> (String, SourcedValue)
+{
+    let funcs =
+        funcs
+            .iter()
+            .map(|(func_name, f)| {
+                let full_name = format!("std.{name}.{func_name}");
+
+                (
+                    (*func_name).to_string(),
+                    value::new_built_in_func(full_name, *f),
+                )
+            })
+            .collect();
+
+    (name.to_string(), value::new_object(funcs, &Mutability::Const))
+}
+
+// `new_io_err` returns a runtime error, so that filesystem errors can be
+// caught using `?`.
+fn new_io_err(verb: &str, path: &Path, source: &io::Error) -> Error {
+    Error::Runtime{msg: format!(
+        "couldn't {verb} '{}': {source}",
+        path.display(),
+    )}
+}
+
+// `new_io_transfer_err` is like `new_io_err`, but for operations that move
+// data from `src` to `dst`, where either path could have caused the error.
+fn new_io_transfer_err(verb: &str, src: &Path, dst: &Path, source: &io::Error)
+    -> Error
+{
+    Error::Runtime{msg: format!(
+        "couldn't {verb} '{}' to '{}': {source}",
+        src.display(),
+        dst.display(),
+    )}
+}
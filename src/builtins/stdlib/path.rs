@@ -0,0 +1,310 @@
+// Copyright 2026 Sean Kelleher. All rights reserved.
+// Use of this source code is governed by an MIT
+// licence that can be found in the LICENCE file.
+
+use std::ffi::OsStr;
+use std::os::unix::ffi::OsStrExt;
+use std::path;
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+
+use snafu::ResultExt;
+
+use super::new_io_err;
+use crate::builtins::fns;
+use crate::eval::error::AssertArgsFailed;
+use crate::eval::error::Error;
+use crate::eval::error::Result;
+use crate::eval::EvaluationContext;
+use crate::eval::value;
+use crate::eval::value::SourcedValue;
+use crate::lock_deref;
+
+// `join` joins its arguments into a single path. An absolute argument replaces
+// the path that has been joined so far, as it does in most shells.
+#[allow(clippy::needless_pass_by_value)]
+pub fn join(
+    _context: &EvaluationContext,
+    _loc: (usize, usize),
+    _this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    if args.is_empty() {
+        return Err(Error::BuiltinFuncErr{
+            msg: "`join` takes at least 1 argument (got 0)".to_string(),
+        });
+    }
+
+    let mut path = PathBuf::new();
+    for (i, arg) in args.iter().enumerate() {
+        path.push(fns::assert_path(&format!("args[{i}]"), arg)?);
+    }
+
+    Ok(new_path_value(&path))
+}
+
+// `dirname` returns `path` with its last component removed. It returns "."
+// for a path that has a single relative component.
+#[allow(clippy::needless_pass_by_value)]
+pub fn dirname(
+    _context: &EvaluationContext,
+    _loc: (usize, usize),
+    _this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("dirname", 1, &args)
+        .context(AssertArgsFailed)?;
+
+    let path = fns::assert_path("path", &args[0])?;
+
+    let dir =
+        match path.parent() {
+            Some(p) if p.as_os_str().is_empty() => Path::new("."),
+            Some(p) => p,
+            None => &path,
+        };
+
+    Ok(new_path_value(dir))
+}
+
+// `basename` returns the last component of `path`, or an empty string if
+// `path` doesn't end in a file name, as is the case for `/` and `..`.
+#[allow(clippy::needless_pass_by_value)]
+pub fn basename(
+    _context: &EvaluationContext,
+    _loc: (usize, usize),
+    _this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("basename", 1, &args)
+        .context(AssertArgsFailed)?;
+
+    let path = fns::assert_path("path", &args[0])?;
+
+    Ok(new_os_str_value(path.file_name()))
+}
+
+// `extension` returns the extension of the last component of `path`, without
+// the leading `.`, or an empty string if it doesn't have one.
+#[allow(clippy::needless_pass_by_value)]
+pub fn extension(
+    _context: &EvaluationContext,
+    _loc: (usize, usize),
+    _this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("extension", 1, &args)
+        .context(AssertArgsFailed)?;
+
+    let path = fns::assert_path("path", &args[0])?;
+
+    Ok(new_os_str_value(path.extension()))
+}
+
+// `stem` returns the last component of `path` without its extension.
+#[allow(clippy::needless_pass_by_value)]
+pub fn stem(
+    _context: &EvaluationContext,
+    _loc: (usize, usize),
+    _this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("stem", 1, &args)
+        .context(AssertArgsFailed)?;
+
+    let path = fns::assert_path("path", &args[0])?;
+
+    Ok(new_os_str_value(path.file_stem()))
+}
+
+// `absolute` returns `path` as an absolute path, without accessing the
+// filesystem or resolving symbolic links.
+#[allow(clippy::needless_pass_by_value)]
+pub fn absolute(
+    context: &EvaluationContext,
+    _loc: (usize, usize),
+    _this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args_between("absolute", 1, 2, &args)
+        .context(AssertArgsFailed)?;
+
+    let path = fns::assert_path("path", &args[0])?;
+    let resolved_path = resolve_path(context, "absolute", &path, args.get(1))?;
+
+    let abs_path =
+        match path::absolute(&resolved_path) {
+            Ok(p) => p,
+            Err(e) => return Err(new_io_err("resolve", &path, &e)),
+        };
+
+    Ok(new_path_value(&abs_path))
+}
+
+// `canonicalize` returns the absolute form of `path`, with all intermediate
+// components normalised and symbolic links resolved. `path` must exist.
+#[allow(clippy::needless_pass_by_value)]
+pub fn canonicalize(
+    context: &EvaluationContext,
+    _loc: (usize, usize),
+    _this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args_between("canonicalize", 1, 2, &args)
+        .context(AssertArgsFailed)?;
+
+    let path = fns::assert_path("path", &args[0])?;
+    let resolved_path =
+        resolve_path(context, "canonicalize", &path, args.get(1))?;
+
+    let canon_path =
+        match resolved_path.canonicalize() {
+            Ok(p) => p,
+            Err(e) => return Err(new_io_err("canonicalize", &path, &e)),
+        };
+
+    Ok(new_path_value(&canon_path))
+}
+
+// `resolve_path` returns `path` resolved relative to the working directory of
+// the script, or relative to the directory of the current script if the
+// `from_script_dir` option is set in `opts`.
+fn resolve_path(
+    context: &EvaluationContext,
+    fn_name: &str,
+    path: &Path,
+    opts: Option<&SourcedValue>,
+)
+    -> Result<PathBuf>
+{
+    let mut from_script_dir = false;
+    if let Some(opts) = opts {
+        from_script_dir =
+            fns::assert_bool_opt(fn_name, "from_script_dir", opts)?;
+    }
+
+    if from_script_dir {
+        Ok(context.cur_script_dir.join(path))
+    } else {
+        Ok(context.resolve_path(path))
+    }
+}
+
+// `relative_to` returns a relative path that leads from `base` to `path`,
+// which may start with `..` components. It works on the components of the
+// paths alone, so `path` and `base` must either both be absolute or both be
+// relative.
+#[allow(clippy::needless_pass_by_value)]
+pub fn relative_to(
+    _context: &EvaluationContext,
+    _loc: (usize, usize),
+    _this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("relative_to", 2, &args)
+        .context(AssertArgsFailed)?;
+
+    let path = fns::assert_path("path", &args[0])?;
+    let base = fns::assert_path("base", &args[1])?;
+
+    if path.is_absolute() != base.is_absolute() {
+        return Err(Error::BuiltinFuncErr{msg: format!(
+            "'{}' and '{}' must both be absolute or both be relative",
+            path.display(),
+            base.display(),
+        )});
+    }
+
+    let path_comps: Vec<Component> = path.components().collect();
+    let base_comps: Vec<Component> = base.components().collect();
+
+    let common = path_comps.iter()
+        .zip(&base_comps)
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let mut rel_path = PathBuf::new();
+    for comp in &base_comps[common..] {
+        if *comp == Component::ParentDir {
+            return Err(Error::BuiltinFuncErr{msg: format!(
+                "can't find '{}' relative to '{}'",
+                path.display(),
+                base.display(),
+            )});
+        }
+        rel_path.push("..");
+    }
+    for comp in &path_comps[common..] {
+        rel_path.push(comp);
+    }
+
+    if rel_path.as_os_str().is_empty() {
+        rel_path.push(".");
+    }
+
+    Ok(new_path_value(&rel_path))
+}
+
+// `expand_user` replaces a leading `~` in `path` with the value of the `HOME`
+// environment variable. Other paths, including those that start with `~user`,
+// are returned unchanged.
+#[allow(clippy::needless_pass_by_value)]
+pub fn expand_user(
+    context: &EvaluationContext,
+    _loc: (usize, usize),
+    _this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("expand_user", 1, &args)
+        .context(AssertArgsFailed)?;
+
+    let path = fns::assert_path("path", &args[0])?;
+
+    let rest =
+        match path.strip_prefix("~") {
+            Ok(rest) => rest,
+            Err(_) => return Ok(new_path_value(&path)),
+        };
+
+    let env = &context.env;
+    let home =
+        match lock_deref!(env).get("HOME") {
+            Some(v) => fns::assert_path("HOME", v)?,
+            None => return Err(Error::Runtime{
+                msg: "couldn't expand '~': `HOME` isn't set".to_string(),
+            }),
+        };
+
+    if rest.as_os_str().is_empty() {
+        Ok(new_path_value(&home))
+    } else {
+        Ok(new_path_value(&home.join(rest)))
+    }
+}
+
+fn new_path_value(path: &Path) -> SourcedValue {
+    value::new_str(path.as_os_str().as_bytes().to_vec())
+}
+
+fn new_os_str_value(s: Option<&OsStr>) -> SourcedValue {
+    value::new_str(s.map(|s| s.as_bytes().to_vec()).unwrap_or_default())
+}
@@ -0,0 +1,251 @@
+// Copyright 2026 Sean Kelleher. All rights reserved.
+// Use of this source code is governed by an MIT
+// licence that can be found in the LICENCE file.
+
+use snafu::ResultExt;
+
+use crate::builtins::fns;
+use crate::eval::error;
+use crate::eval::error::AssertArgsFailed;
+use crate::eval::error::Error;
+use crate::eval::error::Result;
+use crate::eval::EvaluationContext;
+use crate::eval::proc;
+use crate::eval::proc::Cmd;
+use crate::eval::proc::Redirects;
+use crate::eval::proc::Stdin;
+use crate::eval::value;
+use crate::eval::value::ObjectRef;
+use crate::eval::value::SourcedValue;
+use crate::eval::value::Str;
+use crate::eval::value::Value;
+use crate::lock_deref;
+
+// `run` runs the program named by the first item of the `argv` list, with
+// its standard streams redirected according to the optional `opts` object.
+#[allow(clippy::needless_pass_by_value)]
+pub fn run(
+    context: &EvaluationContext,
+    _loc: (usize, usize),
+    _this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args_between("run", 1, 2, &args)
+        .context(AssertArgsFailed)?;
+
+    let argv = assert_argv(&args[0])?;
+    if argv.is_empty() {
+        return Err(Error::EmptyCommand);
+    }
+
+    let Opts{redirects, env_overrides, check} =
+        match args.get(1) {
+            Some(opts) => new_opts(opts)?,
+            None => Opts::default(),
+        };
+
+    let env = proc::new_env(&context.env, env_overrides.as_ref())?;
+    let cmd = Cmd{argv, env, dir: context.cwd()};
+
+    let output =
+        match proc::run(&cmd, redirects) {
+            Ok(v) => v,
+            Err(e) => return Err(Error::Runtime{
+                msg: proc::render_run_error(&cmd.argv, &e),
+            }),
+        };
+
+    if check {
+        let cmd_line = proc::render_argv(&cmd.argv);
+        let maybe_failure =
+            proc::render_failure(&cmd_line, output.exit_code, &output.stderr);
+        if let Some(msg) = maybe_failure {
+            return Err(Error::Runtime{msg});
+        }
+    }
+
+    Ok(proc::new_output_object(output))
+}
+
+// `pipefail` sets whether the pipelines of the current script or module fail
+// when any of their stages fail, rather than only when their last stage fails.
+#[allow(clippy::needless_pass_by_value)]
+pub fn pipefail(
+    context: &EvaluationContext,
+    _loc: (usize, usize),
+    _this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("pipefail", 1, &args)
+        .context(AssertArgsFailed)?;
+
+    let enabled = fns::assert_bool("enabled", &args[0])?;
+
+    context.set_pipefail(enabled);
+
+    Ok(value::new_null())
+}
+
+fn assert_argv(v: &SourcedValue) -> Result<Vec<Str>> {
+    let items =
+        match &v.v {
+            Value::List{items, ..} => items,
+            value => return Err(Error::IncorrectType{
+                descr: "`argv`".to_string(),
+                exp_type: "list".to_string(),
+                value: value.clone(),
+            }),
+        };
+
+    let mut argv = vec![];
+    for (i, item) in lock_deref!(items).iter().enumerate() {
+        match &item.v {
+            Value::Str(s) => {
+                argv.push(s.clone());
+            },
+            value => {
+                return Err(Error::IncorrectType{
+                    descr: format!("`argv` item {i}"),
+                    exp_type: "string".to_string(),
+                    value: value.clone(),
+                });
+            },
+        }
+    }
+
+    Ok(argv)
+}
+
+struct Opts {
+    redirects: Redirects,
+    env_overrides: Option<ObjectRef>,
+    // `check` defines whether a non-zero exit code results in an error.
+    check: bool,
+}
+
+impl Default for Opts {
+    fn default() -> Self {
+        Opts{
+            redirects: Redirects::default(),
+            env_overrides: None,
+            check: true,
+        }
+    }
+}
+
+// `new_opts` creates `Opts` from the properties of the `opts` object.
+fn new_opts(opts: &SourcedValue) -> Result<Opts> {
+    let props =
+        match &opts.v {
+            Value::Object{props, ..} => props,
+            value => return Err(Error::IncorrectType{
+                descr: "`opts`".to_string(),
+                exp_type: "object".to_string(),
+                value: value.clone(),
+            }),
+        };
+
+    let Opts{mut redirects, mut env_overrides, mut check} = Opts::default();
+    for (name, v) in &lock_deref!(props) {
+        match name.as_str() {
+            "stdin" => {
+                if let Stdin::File(_) = redirects.stdin {
+                    return Err(new_conflict_err("stdin", "stdin_file"));
+                }
+                redirects.stdin = Stdin::Bytes(assert_stdin(v)?);
+            },
+            "stdin_file" => {
+                if let Stdin::Bytes(_) = redirects.stdin {
+                    return Err(new_conflict_err("stdin", "stdin_file"));
+                }
+                redirects.stdin = Stdin::File(fns::assert_path(name, v)?);
+            },
+            "stdout_file" => {
+                redirects.stdout_file = Some(fns::assert_path(name, v)?);
+            },
+            "stderr_file" => {
+                redirects.stderr_file = Some(fns::assert_path(name, v)?);
+            },
+            "append" => {
+                redirects.append = fns::assert_bool(name, v)?;
+            },
+            "stderr_to_stdout" => {
+                redirects.stderr_to_stdout = fns::assert_bool(name, v)?;
+            },
+            "env" => {
+                match &v.v {
+                    Value::Object{props, ..} => {
+                        env_overrides = Some(props.clone());
+                    },
+                    value => {
+                        return Err(Error::IncorrectType{
+                            descr: "`env`".to_string(),
+                            exp_type: "object".to_string(),
+                            value: value.clone(),
+                        });
+                    },
+                }
+            },
+            "check" => {
+                check = fns::assert_bool(name, v)?;
+            },
+            _ => {
+                return Err(Error::BuiltinFuncErr{msg: format!(
+                    "`run` doesn't support the '{name}' option",
+                )});
+            },
+        }
+    }
+
+    if redirects.stderr_file.is_some() && redirects.stderr_to_stdout {
+        return Err(new_conflict_err("stderr_file", "stderr_to_stdout"));
+    }
+
+    Ok(Opts{redirects, env_overrides, check})
+}
+
+// `assert_stdin` returns the bytes to write to `stdin` for `v`, which must be
+// either a string, or a list of strings that are each written as a line.
+fn assert_stdin(v: &SourcedValue) -> Result<Str> {
+    match &v.v {
+        Value::Str(s) => {
+            Ok(s.clone())
+        },
+        Value::List{items, ..} => {
+            let mut bytes = vec![];
+            for (i, item) in lock_deref!(items).iter().enumerate() {
+                match &item.v {
+                    Value::Str(line) => {
+                        bytes.extend(line);
+                        bytes.push(b'\n');
+                    },
+                    value => {
+                        return Err(Error::IncorrectType{
+                            descr: format!("`stdin` line {i}"),
+                            exp_type: "string".to_string(),
+                            value: value.clone(),
+                        });
+                    },
+                }
+            }
+
+            Ok(bytes)
+        },
+        value => {
+            Err(Error::BuiltinFuncErr{msg: format!(
+                "`stdin` must be a string or a list of strings, got '{}'",
+                error::render_type(value),
+            )})
+        },
+    }
+}
+
+fn new_conflict_err(a: &str, b: &str) -> Error {
+    Error::BuiltinFuncErr{msg: format!(
+        "`{a}` and `{b}` can't both be set",
+    )}
+}
@@ -0,0 +1,56 @@
+// Copyright 2026 Sean Kelleher. All rights reserved.
+// Use of this source code is governed by an MIT
+// licence that can be found in the LICENCE file.
+
+use snafu::ResultExt;
+
+use crate::builtins::fns;
+use crate::eval::error::AssertArgsFailed;
+use crate::eval::error::Error;
+use crate::eval::error::Result;
+use crate::eval::EvaluationContext;
+use crate::eval::value;
+use crate::eval::value::SourcedValue;
+
+// `parse_int` returns the int that `s` represents in `base`, which is optional
+// and defaults to 10. `s` may start with a `+` or `-` sign.
+#[allow(clippy::needless_pass_by_value)]
+pub fn parse_int(
+    _context: &EvaluationContext,
+    _loc: (usize, usize),
+    _this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args_between("parse_int", 1, 2, &args)
+        .context(AssertArgsFailed)?;
+
+    let s = fns::assert_raw_str("s", &args[0])?;
+
+    let mut base = 10;
+    if let Some(v) = args.get(1) {
+        base = fns::assert_int("base", v)?;
+    }
+
+    let radix =
+        match u32::try_from(base) {
+            Ok(radix) if (2..=36).contains(&radix) => radix,
+            _ => return Err(Error::BuiltinFuncErr{msg: format!(
+                "`parse_int` requires `base` to be between 2 and 36 (got \
+                 {base})",
+            )}),
+        };
+
+    let maybe_n = String::from_utf8(s.clone())
+        .ok()
+        .and_then(|s| i64::from_str_radix(&s, radix).ok());
+
+    match maybe_n {
+        Some(n) => Ok(value::new_int(n)),
+        None => Err(Error::Runtime{msg: format!(
+            "couldn't parse '{}' as a base-{base} int",
+            String::from_utf8_lossy(&s),
+        )}),
+    }
+}
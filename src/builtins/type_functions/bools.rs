@@ -0,0 +1,33 @@
+// Copyright 2026 Sean Kelleher. All rights reserved.
+// Use of this source code is governed by an MIT
+// licence that can be found in the LICENCE file.
+
+use snafu::ResultExt;
+
+use crate::builtins::fns;
+use crate::eval::error::AssertArgsFailed;
+use crate::eval::error::AssertThisFailed;
+use crate::eval::error::Result;
+use crate::eval::EvaluationContext;
+use crate::eval::value;
+use crate::eval::value::SourcedValue;
+
+#[allow(clippy::needless_pass_by_value)]
+pub fn to_str(
+    _context: &EvaluationContext,
+    _loc: (usize, usize),
+    this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("to_str", 0, &args)
+        .context(AssertArgsFailed)?;
+
+    let this = fns::assert_this(this)
+        .context(AssertThisFailed)?;
+
+    let b = fns::assert_bool("this", &this)?;
+
+    Ok(value::new_str_from_string(b.to_string()))
+}
@@ -0,0 +1,111 @@
+// Copyright 2026 Sean Kelleher. All rights reserved.
+// Use of this source code is governed by an MIT
+// licence that can be found in the LICENCE file.
+
+use snafu::ResultExt;
+
+use crate::builtins::fns;
+use crate::eval::error::AssertArgsFailed;
+use crate::eval::error::AssertThisFailed;
+use crate::eval::error::Error;
+use crate::eval::error::Result;
+use crate::eval::EvaluationContext;
+use crate::eval::value;
+use crate::eval::value::SourcedValue;
+
+#[allow(clippy::needless_pass_by_value)]
+pub fn to_str(
+    _context: &EvaluationContext,
+    _loc: (usize, usize),
+    this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("to_str", 0, &args)
+        .context(AssertArgsFailed)?;
+
+    let n = assert_this_float(this)?;
+
+    Ok(value::new_str_from_string(fns::render_float(n)))
+}
+
+// `round` returns the int closest to the float, rounding half-way cases away
+// from zero.
+#[allow(clippy::needless_pass_by_value)]
+pub fn round(
+    _context: &EvaluationContext,
+    _loc: (usize, usize),
+    this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("round", 0, &args)
+        .context(AssertArgsFailed)?;
+
+    let n = assert_this_float(this)?;
+
+    to_int("round", n, n.round())
+}
+
+// `floor` returns the largest int less than or equal to the float.
+#[allow(clippy::needless_pass_by_value)]
+pub fn floor(
+    _context: &EvaluationContext,
+    _loc: (usize, usize),
+    this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("floor", 0, &args)
+        .context(AssertArgsFailed)?;
+
+    let n = assert_this_float(this)?;
+
+    to_int("floor", n, n.floor())
+}
+
+// `ceil` returns the smallest int greater than or equal to the float.
+#[allow(clippy::needless_pass_by_value)]
+pub fn ceil(
+    _context: &EvaluationContext,
+    _loc: (usize, usize),
+    this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("ceil", 0, &args)
+        .context(AssertArgsFailed)?;
+
+    let n = assert_this_float(this)?;
+
+    to_int("ceil", n, n.ceil())
+}
+
+fn assert_this_float(this: Option<SourcedValue>) -> Result<f64> {
+    let this = fns::assert_this(this)
+        .context(AssertThisFailed)?;
+
+    fns::assert_float("this", &this)
+}
+
+// `to_int` returns `rounded`, which is the result of rounding `n` using
+// `fn_name`, as an int, if it's in the range of an int.
+fn to_int(fn_name: &str, n: f64, rounded: f64) -> Result<SourcedValue> {
+    // `i64::MIN` is exactly representable as a float, but `i64::MAX` isn't,
+    // so we check against `-i64::MIN` as an exclusive upper bound.
+    #[allow(clippy::cast_precision_loss)]
+    let min = i64::MIN as f64;
+    if rounded >= min && rounded < -min {
+        #[allow(clippy::cast_possible_truncation)]
+        return Ok(value::new_int(rounded as i64));
+    }
+
+    Err(Error::Runtime{msg: format!(
+        "'{}::{fn_name}()' is outside the range of an 'int'",
+        fns::render_float(n),
+    )})
+}
@@ -0,0 +1,152 @@
+// Copyright 2026 Sean Kelleher. All rights reserved.
+// Use of this source code is governed by an MIT
+// licence that can be found in the LICENCE file.
+
+use snafu::ResultExt;
+
+use crate::builtins::fns;
+use crate::eval::error::AssertArgsFailed;
+use crate::eval::error::AssertThisFailed;
+use crate::eval::error::Error;
+use crate::eval::error::Result;
+use crate::eval::EvaluationContext;
+use crate::eval::value;
+use crate::eval::value::SourcedValue;
+
+#[allow(clippy::needless_pass_by_value)]
+pub fn to_str(
+    _context: &EvaluationContext,
+    _loc: (usize, usize),
+    this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("to_str", 0, &args)
+        .context(AssertArgsFailed)?;
+
+    let n = assert_this_int(this)?;
+
+    Ok(value::new_str_from_string(n.to_string()))
+}
+
+// `to_float` returns the float closest to the int.
+#[allow(clippy::needless_pass_by_value)]
+pub fn to_float(
+    _context: &EvaluationContext,
+    _loc: (usize, usize),
+    this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("to_float", 0, &args)
+        .context(AssertArgsFailed)?;
+
+    let n = assert_this_int(this)?;
+
+    #[allow(clippy::cast_precision_loss)]
+    Ok(value::new_float(n as f64))
+}
+
+#[allow(clippy::needless_pass_by_value)]
+pub fn abs(
+    _context: &EvaluationContext,
+    _loc: (usize, usize),
+    this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("abs", 0, &args)
+        .context(AssertArgsFailed)?;
+
+    let n = assert_this_int(this)?;
+
+    match n.checked_abs() {
+        Some(v) => Ok(value::new_int(v)),
+        None => Err(new_int_overflow(&format!("{n}::abs()"))),
+    }
+}
+
+// `min` returns the smaller of the int and `other`.
+#[allow(clippy::needless_pass_by_value)]
+pub fn min(
+    _context: &EvaluationContext,
+    _loc: (usize, usize),
+    this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("min", 1, &args)
+        .context(AssertArgsFailed)?;
+
+    let n = assert_this_int(this)?;
+    let other = fns::assert_int("other", &args[0])?;
+
+    Ok(value::new_int(n.min(other)))
+}
+
+// `max` returns the larger of the int and `other`.
+#[allow(clippy::needless_pass_by_value)]
+pub fn max(
+    _context: &EvaluationContext,
+    _loc: (usize, usize),
+    this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("max", 1, &args)
+        .context(AssertArgsFailed)?;
+
+    let n = assert_this_int(this)?;
+    let other = fns::assert_int("other", &args[0])?;
+
+    Ok(value::new_int(n.max(other)))
+}
+
+// `pow` returns the int raised to the power of `exp`, which must be
+// non-negative.
+#[allow(clippy::needless_pass_by_value)]
+pub fn pow(
+    _context: &EvaluationContext,
+    _loc: (usize, usize),
+    this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("pow", 1, &args)
+        .context(AssertArgsFailed)?;
+
+    let n = assert_this_int(this)?;
+    let exp = fns::assert_int("exp", &args[0])?;
+
+    if exp < 0 {
+        return Err(Error::BuiltinFuncErr{msg: format!(
+            "`pow` requires `exp` to be non-negative (got {exp})",
+        )});
+    }
+
+    let maybe_v = u32::try_from(exp)
+        .ok()
+        .and_then(|e| n.checked_pow(e));
+
+    match maybe_v {
+        Some(v) => Ok(value::new_int(v)),
+        None => Err(new_int_overflow(&format!("{n}::pow({exp})"))),
+    }
+}
+
+fn assert_this_int(this: Option<SourcedValue>) -> Result<i64> {
+    let this = fns::assert_this(this)
+        .context(AssertThisFailed)?;
+
+    fns::assert_int("this", &this)
+}
+
+fn new_int_overflow(call: &str) -> Error {
+    Error::Runtime{msg: format!("'{call}' caused an integer overflow")}
+}
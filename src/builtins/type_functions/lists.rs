@@ -0,0 +1,669 @@
+// Copyright 2026 Sean Kelleher. All rights reserved.
+// Use of this source code is governed by an MIT
+// licence that can be found in the LICENCE file.
+
+use std::cmp::Ordering;
+
+use snafu::ResultExt;
+
+use crate::builtins::fns;
+use crate::eval;
+use crate::eval::error::AssertArgsFailed;
+use crate::eval::error::AssertThisFailed;
+use crate::eval::error::CastFailed;
+use crate::eval::error::Error;
+use crate::eval::error::Result;
+use crate::eval::EvaluationContext;
+use crate::eval::scope::Mutability;
+use crate::eval::value;
+use crate::eval::value::List;
+use crate::eval::value::ListRef;
+use crate::eval::value::SourcedValue;
+use crate::eval::value::Value;
+use crate::lock_deref;
+
+// Note that functions that take callbacks operate on a copy of the list, so
+// that callbacks can safely access the list. Lists that are returned by
+// non-mutating functions have the same mutability as the original list.
+
+#[allow(clippy::needless_pass_by_value)]
+pub fn push(
+    _context: &EvaluationContext,
+    _loc: (usize, usize),
+    this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("push", 1, &args)
+        .context(AssertArgsFailed)?;
+
+    let items = assert_this_mut_list("push", this)?;
+
+    lock_deref!(items).push(args[0].clone());
+
+    Ok(value::new_null())
+}
+
+// `pop` removes the last item of the list and returns it.
+#[allow(clippy::needless_pass_by_value)]
+pub fn pop(
+    _context: &EvaluationContext,
+    _loc: (usize, usize),
+    this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("pop", 0, &args)
+        .context(AssertArgsFailed)?;
+
+    let items = assert_this_mut_list("pop", this)?;
+
+    let maybe_item = lock_deref!(items).pop();
+
+    match maybe_item {
+        Some(item) => Ok(item),
+        None => Err(Error::Runtime{
+            msg: "can't pop from an empty list".to_string(),
+        }),
+    }
+}
+
+// `insert` inserts `v` into the list at `index`, shifting the items after it
+// to the right.
+#[allow(clippy::needless_pass_by_value)]
+pub fn insert(
+    _context: &EvaluationContext,
+    _loc: (usize, usize),
+    this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("insert", 2, &args)
+        .context(AssertArgsFailed)?;
+
+    let items = assert_this_mut_list("insert", this)?;
+    let index = fns::assert_int("index", &args[0])?;
+
+    let mut items = items.try_lock().unwrap();
+
+    let i = assert_index(index, items.len() + 1)?;
+    items.insert(i, args[1].clone());
+
+    Ok(value::new_null())
+}
+
+// `remove` removes the item at `index` from the list and returns it.
+#[allow(clippy::needless_pass_by_value)]
+pub fn remove(
+    _context: &EvaluationContext,
+    _loc: (usize, usize),
+    this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("remove", 1, &args)
+        .context(AssertArgsFailed)?;
+
+    let items = assert_this_mut_list("remove", this)?;
+    let index = fns::assert_int("index", &args[0])?;
+
+    let mut items = items.try_lock().unwrap();
+
+    let i = assert_index(index, items.len())?;
+
+    Ok(items.remove(i))
+}
+
+#[allow(clippy::needless_pass_by_value)]
+pub fn clear(
+    _context: &EvaluationContext,
+    _loc: (usize, usize),
+    this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("clear", 0, &args)
+        .context(AssertArgsFailed)?;
+
+    let items = assert_this_mut_list("clear", this)?;
+
+    lock_deref!(items).clear();
+
+    Ok(value::new_null())
+}
+
+// `sort_in_place` sorts the list, using the values returned by the optional
+// `key` function to order items.
+#[allow(clippy::needless_pass_by_value)]
+pub fn sort_in_place(
+    context: &EvaluationContext,
+    loc: (usize, usize),
+    this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args_between("sort_in_place", 0, 1, &args)
+        .context(AssertArgsFailed)?;
+
+    let items = assert_this_mut_list("sort_in_place", this)?;
+
+    let unsorted = lock_deref!(items).clone();
+    let sorted = sort("sort_in_place", context, loc, unsorted, args.first())?;
+
+    *items.try_lock().unwrap() = sorted;
+
+    Ok(value::new_null())
+}
+
+#[allow(clippy::needless_pass_by_value)]
+pub fn len(
+    _context: &EvaluationContext,
+    _loc: (usize, usize),
+    this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("len", 0, &args)
+        .context(AssertArgsFailed)?;
+
+    let (items, _) = assert_this_list(this)?;
+
+    let n: i64 = lock_deref!(items).len().try_into()
+        .context(CastFailed)?;
+
+    Ok(value::new_int(n))
+}
+
+// `contains` returns whether the list contains an item that's equal to `v`.
+#[allow(clippy::needless_pass_by_value)]
+pub fn contains(
+    _context: &EvaluationContext,
+    _loc: (usize, usize),
+    this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("contains", 1, &args)
+        .context(AssertArgsFailed)?;
+
+    let (items, _) = assert_this_list(this)?;
+
+    let found = copy_items(&items).iter()
+        .any(|item| is_eq(item, &args[0]));
+
+    Ok(value::new_bool(found))
+}
+
+// `map` returns a list of the results of calling `f` on each item.
+#[allow(clippy::needless_pass_by_value)]
+pub fn map(
+    context: &EvaluationContext,
+    loc: (usize, usize),
+    this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("map", 1, &args)
+        .context(AssertArgsFailed)?;
+
+    let (items, is_mutable) = assert_this_list(this)?;
+    let f = fns::assert_func("f", &args[0])?;
+
+    let mut mapped = vec![];
+    for item in copy_items(&items) {
+        mapped.push(fns::call(context, loc, &f, vec![item])?);
+    }
+
+    Ok(value::new_list_with_mutability(mapped, is_mutable))
+}
+
+// `filter` returns a list of the items that `f` returns `true` for.
+#[allow(clippy::needless_pass_by_value)]
+pub fn filter(
+    context: &EvaluationContext,
+    loc: (usize, usize),
+    this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("filter", 1, &args)
+        .context(AssertArgsFailed)?;
+
+    let (items, is_mutable) = assert_this_list(this)?;
+    let f = fns::assert_func("f", &args[0])?;
+
+    let mut filtered = vec![];
+    for item in copy_items(&items) {
+        if call_predicate(context, loc, &f, &item)? {
+            filtered.push(item);
+        }
+    }
+
+    Ok(value::new_list_with_mutability(filtered, is_mutable))
+}
+
+// `reduce` returns the result of calling `f` on an accumulator and each item
+// in turn, where the accumulator starts as `init` and is then replaced by the
+// result of each call.
+#[allow(clippy::needless_pass_by_value)]
+pub fn reduce(
+    context: &EvaluationContext,
+    loc: (usize, usize),
+    this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("reduce", 2, &args)
+        .context(AssertArgsFailed)?;
+
+    let (items, _) = assert_this_list(this)?;
+    let f = fns::assert_func("f", &args[0])?;
+
+    let mut acc = args[1].clone();
+    for item in copy_items(&items) {
+        acc = fns::call(context, loc, &f, vec![acc, item])?;
+    }
+
+    Ok(acc)
+}
+
+// `any` returns whether `f` returns `true` for any item. `f` isn't called on
+// the items after the first item that it returns `true` for.
+#[allow(clippy::needless_pass_by_value)]
+pub fn any(
+    context: &EvaluationContext,
+    loc: (usize, usize),
+    this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("any", 1, &args)
+        .context(AssertArgsFailed)?;
+
+    let (items, _) = assert_this_list(this)?;
+    let f = fns::assert_func("f", &args[0])?;
+
+    for item in copy_items(&items) {
+        if call_predicate(context, loc, &f, &item)? {
+            return Ok(value::new_bool(true));
+        }
+    }
+
+    Ok(value::new_bool(false))
+}
+
+// `all` returns whether `f` returns `true` for every item. `f` isn't called on
+// the items after the first item that it returns `false` for.
+#[allow(clippy::needless_pass_by_value)]
+pub fn all(
+    context: &EvaluationContext,
+    loc: (usize, usize),
+    this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("all", 1, &args)
+        .context(AssertArgsFailed)?;
+
+    let (items, _) = assert_this_list(this)?;
+    let f = fns::assert_func("f", &args[0])?;
+
+    for item in copy_items(&items) {
+        if !call_predicate(context, loc, &f, &item)? {
+            return Ok(value::new_bool(false));
+        }
+    }
+
+    Ok(value::new_bool(true))
+}
+
+// `find` returns the first item that `f` returns `true` for, or `null` if `f`
+// doesn't return `true` for any item.
+#[allow(clippy::needless_pass_by_value)]
+pub fn find(
+    context: &EvaluationContext,
+    loc: (usize, usize),
+    this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("find", 1, &args)
+        .context(AssertArgsFailed)?;
+
+    let (items, _) = assert_this_list(this)?;
+    let f = fns::assert_func("f", &args[0])?;
+
+    for item in copy_items(&items) {
+        if call_predicate(context, loc, &f, &item)? {
+            return Ok(item);
+        }
+    }
+
+    Ok(value::new_null())
+}
+
+// `sorted` returns a sorted copy of the list, using the values returned by the
+// optional `key` function to order items.
+#[allow(clippy::needless_pass_by_value)]
+pub fn sorted(
+    context: &EvaluationContext,
+    loc: (usize, usize),
+    this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args_between("sorted", 0, 1, &args)
+        .context(AssertArgsFailed)?;
+
+    let (items, is_mutable) = assert_this_list(this)?;
+
+    let sorted =
+        sort("sorted", context, loc, copy_items(&items), args.first())?;
+
+    Ok(value::new_list_with_mutability(sorted, is_mutable))
+}
+
+#[allow(clippy::needless_pass_by_value)]
+pub fn reversed(
+    _context: &EvaluationContext,
+    _loc: (usize, usize),
+    this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("reversed", 0, &args)
+        .context(AssertArgsFailed)?;
+
+    let (items, is_mutable) = assert_this_list(this)?;
+
+    let mut reversed = copy_items(&items);
+    reversed.reverse();
+
+    Ok(value::new_list_with_mutability(reversed, is_mutable))
+}
+
+// `join` returns the items of the list, which must be strings, joined by
+// `sep`.
+#[allow(clippy::needless_pass_by_value)]
+pub fn join(
+    _context: &EvaluationContext,
+    _loc: (usize, usize),
+    this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("join", 1, &args)
+        .context(AssertArgsFailed)?;
+
+    let (items, _) = assert_this_list(this)?;
+    let sep = fns::assert_raw_str("sep", &args[0])?;
+
+    let mut strs = vec![];
+    for (i, item) in lock_deref!(items).iter().enumerate() {
+        strs.push(fns::assert_raw_str(&format!("this[{i}]"), item)?);
+    }
+
+    Ok(value::new_str(strs.join(&sep[..])))
+}
+
+// `enumerate` returns a list of `[index, item]` pairs for the items of the
+// list.
+#[allow(clippy::needless_pass_by_value)]
+pub fn enumerate(
+    _context: &EvaluationContext,
+    _loc: (usize, usize),
+    this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("enumerate", 0, &args)
+        .context(AssertArgsFailed)?;
+
+    let (items, is_mutable) = assert_this_list(this)?;
+
+    let mut pairs = vec![];
+    for (i, item) in copy_items(&items).into_iter().enumerate() {
+        let index = i.try_into()
+            .context(CastFailed)?;
+
+        pairs.push(new_pair(value::new_int(index), item));
+    }
+
+    Ok(value::new_list_with_mutability(pairs, is_mutable))
+}
+
+// `zip` returns a list of `[a, b]` pairs, where `a` is an item of the list and
+// `b` is the item of `other` at the same index. The returned list is as long
+// as the shorter of the two lists.
+#[allow(clippy::needless_pass_by_value)]
+pub fn zip(
+    _context: &EvaluationContext,
+    _loc: (usize, usize),
+    this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("zip", 1, &args)
+        .context(AssertArgsFailed)?;
+
+    let (items, is_mutable) = assert_this_list(this)?;
+    let other = assert_list("other", &args[0])?;
+
+    let pairs = copy_items(&items).into_iter()
+        .zip(copy_items(&other))
+        .map(|(a, b)| new_pair(a, b))
+        .collect();
+
+    Ok(value::new_list_with_mutability(pairs, is_mutable))
+}
+
+// `flat` returns the list with the items of any nested lists inlined. Only one
+// level of nesting is removed.
+#[allow(clippy::needless_pass_by_value)]
+pub fn flat(
+    _context: &EvaluationContext,
+    _loc: (usize, usize),
+    this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("flat", 0, &args)
+        .context(AssertArgsFailed)?;
+
+    let (items, is_mutable) = assert_this_list(this)?;
+
+    let mut flattened = vec![];
+    for item in copy_items(&items) {
+        if let Value::List{items: inner, ..} = &item.v {
+            flattened.extend(copy_items(inner));
+        } else {
+            flattened.push(item);
+        }
+    }
+
+    Ok(value::new_list_with_mutability(flattened, is_mutable))
+}
+
+// `unique` returns the list with any items that are equal to an earlier item
+// removed.
+#[allow(clippy::needless_pass_by_value)]
+pub fn unique(
+    _context: &EvaluationContext,
+    _loc: (usize, usize),
+    this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("unique", 0, &args)
+        .context(AssertArgsFailed)?;
+
+    let (items, is_mutable) = assert_this_list(this)?;
+
+    let mut uniques: List = vec![];
+    for item in copy_items(&items) {
+        let seen = uniques.iter().any(|u| is_eq(u, &item));
+
+        if !seen {
+            uniques.push(item);
+        }
+    }
+
+    Ok(value::new_list_with_mutability(uniques, is_mutable))
+}
+
+// `sort` returns `items` sorted by the values returned by `key`, or by the
+// items themselves if `key` isn't defined. The values being sorted by must
+// either all be ints or all be strings.
+fn sort(
+    fn_name: &str,
+    context: &EvaluationContext,
+    loc: (usize, usize),
+    items: List,
+    key: Option<&SourcedValue>,
+)
+    -> Result<List>
+{
+    let mut keyed = vec![];
+    for item in items {
+        let k =
+            match key {
+                Some(f) => {
+                    let f = fns::assert_func("key", f)?;
+
+                    fns::call(context, loc, &f, vec![item.clone()])?
+                },
+                None => {
+                    item.clone()
+                },
+            };
+
+        keyed.push((k, item));
+    }
+
+    let all_ints = keyed.iter().all(|(k, _)| matches!(k.v, Value::Int(_)));
+    let all_strs = keyed.iter().all(|(k, _)| matches!(k.v, Value::Str(_)));
+    if !all_ints && !all_strs {
+        return Err(Error::BuiltinFuncErr{msg: format!(
+            "`{fn_name}` can only sort by 'int's or by 'string's",
+        )});
+    }
+
+    keyed.sort_by(|(a, _), (b, _)| {
+        match (&a.v, &b.v) {
+            (Value::Int(x), Value::Int(y)) => x.cmp(y),
+            (Value::Str(x), Value::Str(y)) => x.cmp(y),
+            _ => Ordering::Equal,
+        }
+    });
+
+    Ok(keyed.into_iter().map(|(_, item)| item).collect())
+}
+
+// `call_predicate` returns the result of calling `f` on `item`, which must be
+// a bool.
+fn call_predicate(
+    context: &EvaluationContext,
+    loc: (usize, usize),
+    f: &SourcedValue,
+    item: &SourcedValue,
+)
+    -> Result<bool>
+{
+    let v = fns::call(context, loc, f, vec![item.clone()])?;
+
+    match v.v {
+        Value::Bool(b) => Ok(b),
+        value => Err(Error::IncorrectType{
+            descr: "the return value of `f`".to_string(),
+            exp_type: "bool".to_string(),
+            value,
+        }),
+    }
+}
+
+fn assert_this_list(this: Option<SourcedValue>) -> Result<(ListRef, bool)> {
+    let this = fns::assert_this(this)
+        .context(AssertThisFailed)?;
+
+    match this.v {
+        Value::List{items, is_mutable} => Ok((items, is_mutable)),
+        value => Err(Error::IncorrectType{
+            descr: "`this`".to_string(),
+            exp_type: "list".to_string(),
+            value,
+        }),
+    }
+}
+
+// `assert_this_mut_list` returns the list that `fn_name` was called on, which
+// must be mutable.
+fn assert_this_mut_list(fn_name: &str, this: Option<SourcedValue>)
+    -> Result<ListRef>
+{
+    let (items, is_mutable) = assert_this_list(this)?;
+    if !is_mutable {
+        return Err(Error::ListNotMutable{assign_type: fn_name.to_string()});
+    }
+
+    Ok(items)
+}
+
+fn assert_list(val_name: &str, v: &SourcedValue) -> Result<ListRef> {
+    match &v.v {
+        Value::List{items, ..} => Ok(items.clone()),
+        value => Err(Error::IncorrectType{
+            descr: format!("`{val_name}`"),
+            exp_type: "list".to_string(),
+            value: value.clone(),
+        }),
+    }
+}
+
+// `assert_index` returns `index` as a `usize`, if it's less than `len`.
+fn assert_index(index: i64, len: usize) -> Result<usize> {
+    if index < 0 {
+        return Err(Error::NegativeIndex{index});
+    }
+
+    match usize::try_from(index) {
+        Ok(i) if i < len => Ok(i),
+        _ => Err(Error::Runtime{msg: format!(
+            "index '{index}' is outside the list bounds",
+        )}),
+    }
+}
+
+// `is_eq` returns whether `a` and `b` are equal, where values of different
+// types are never equal.
+fn is_eq(a: &SourcedValue, b: &SourcedValue) -> bool {
+    eval::eq(&a.v, &b.v).unwrap_or(false)
+}
+
+fn copy_items(items: &ListRef) -> List {
+    lock_deref!(items).clone()
+}
+
+fn new_pair(a: SourcedValue, b: SourcedValue) -> SourcedValue {
+    value::new_list(vec![a, b], &Mutability::Const)
+}
@@ -0,0 +1,182 @@
+// Copyright 2025-2026 Sean Kelleher. All rights reserved.
+// Use of this source code is governed by an MIT
+// licence that can be found in the LICENCE file.
+
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use snafu::ResultExt;
+
+use super::fns;
+use crate::eval::builtins::TypeFunctions;
+use crate::eval::error::AssertArgsFailed;
+use crate::eval::error::AssertThisFailed;
+use crate::eval::error::Result;
+use crate::eval::EvaluationContext;
+use crate::eval::value;
+use crate::eval::value::BuiltinFunc;
+use crate::eval::value::ObjectRef;
+use crate::eval::value::SourcedValue;
+use crate::eval::value::Value;
+
+mod bools;
+mod floats;
+mod ints;
+mod lists;
+mod objects;
+mod strs;
+
+pub fn type_functions() -> TypeFunctions {
+    TypeFunctions{
+        bools: new_type_func_map("bool", &[
+            ("to_str", bools::to_str),
+            ("type", any_type),
+        ]),
+        ints: new_type_func_map("int", &[
+            ("abs", ints::abs),
+            ("max", ints::max),
+            ("min", ints::min),
+            ("pow", ints::pow),
+            ("to_float", ints::to_float),
+            ("to_str", ints::to_str),
+            ("type", any_type),
+        ]),
+        floats: new_type_func_map("float", &[
+            ("ceil", floats::ceil),
+            ("floor", floats::floor),
+            ("round", floats::round),
+            ("to_str", floats::to_str),
+            ("type", any_type),
+        ]),
+        strs: new_type_func_map("str", &[
+            ("contains", strs::contains),
+            ("ends_with", strs::ends_with),
+            ("find", strs::find),
+            ("len", strs::len),
+            ("lines", strs::lines),
+            ("lower", strs::lower),
+            ("pad_left", strs::pad_left),
+            ("pad_right", strs::pad_right),
+            ("repeat", strs::repeat),
+            ("replace", strs::replace),
+            ("split", strs::split),
+            ("starts_with", strs::starts_with),
+            ("trim", strs::trim),
+            ("trim_end", strs::trim_end),
+            ("trim_start", strs::trim_start),
+            ("type", any_type),
+            ("upper", strs::upper),
+        ]),
+        lists: new_type_func_map("list", &[
+            ("all", lists::all),
+            ("any", lists::any),
+            ("clear", lists::clear),
+            ("contains", lists::contains),
+            ("enumerate", lists::enumerate),
+            ("filter", lists::filter),
+            ("find", lists::find),
+            ("flat", lists::flat),
+            ("insert", lists::insert),
+            ("join", lists::join),
+            ("len", lists::len),
+            ("map", lists::map),
+            ("pop", lists::pop),
+            ("push", lists::push),
+            ("reduce", lists::reduce),
+            ("remove", lists::remove),
+            ("reversed", lists::reversed),
+            ("sort_in_place", lists::sort_in_place),
+            ("sorted", lists::sorted),
+            ("type", any_type),
+            ("unique", lists::unique),
+            ("zip", lists::zip),
+        ]),
+        objects: new_type_func_map("object", &[
+            ("entries", objects::entries),
+            ("get", objects::get),
+            ("has", objects::has),
+            ("keys", objects::keys),
+            ("len", objects::len),
+            ("merge", objects::merge),
+            ("remove", objects::remove),
+            ("type", any_type),
+            ("values", objects::values),
+        ]),
+        funcs: new_type_func_map("func", &[
+            ("type", any_type),
+        ]),
+        enums: new_type_func_map("enum", &[
+            ("type", any_type),
+        ]),
+    }
+}
+
+pub fn new_func_map(funcs: Vec<(String, SourcedValue)>) -> ObjectRef {
+    Arc::new(Mutex::new(BTreeMap::<String, SourcedValue>::from_iter(
+        funcs,
+    )))
+}
+
+// `new_type_func_map` returns a map of the functions in `funcs`, where each
+// function is named `<type_name>-><name>`.
+fn new_type_func_map(type_name: &str, funcs: &[(&str, BuiltinFunc)])
+    -> ObjectRef
+{
+    new_func_map(
+        funcs
+            .iter()
+            .map(|(name, f)| {
+                let full_name = format!("{type_name}->{name}");
+
+                ((*name).to_string(), value::new_built_in_func(full_name, *f))
+            })
+            .collect(),
+    )
+}
+
+#[allow(clippy::needless_pass_by_value)]
+pub fn any_type(
+    _context: &EvaluationContext,
+    _loc: (usize, usize),
+    this: Option<SourcedValue>,
+    vs: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("type", 0, &vs)
+        .context(AssertArgsFailed)?;
+
+    let this = fns::assert_this(this)
+        .context(AssertThisFailed)?;
+
+    let s = render_type(&this.v);
+
+    Ok(value::new_str_from_string(s))
+}
+
+// TODO Duplicated from `src/eval/error.rs`.
+fn render_type(v: &Value) -> String {
+    let s =
+        match v {
+            Value::Null => "null",
+
+            Value::Bool(_) => "bool",
+            Value::Int(_) => "int",
+            Value::Float(_) => "float",
+            Value::Str(_) => "string",
+
+            Value::List{..} => "list",
+            Value::Object{..} => "object",
+
+            Value::BuiltinFunc{..} |
+            Value::Func{..} |
+            Value::EnumConstructor{..} => "func",
+
+            Value::Enum(_) => "enum",
+            Value::Variant{typ, ..} => return typ.name.clone(),
+        };
+
+    s.to_string()
+}
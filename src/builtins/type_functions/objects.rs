@@ -0,0 +1,255 @@
+// Copyright 2026 Sean Kelleher. All rights reserved.
+// Use of this source code is governed by an MIT
+// licence that can be found in the LICENCE file.
+
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use snafu::ResultExt;
+
+use crate::builtins::fns;
+use crate::eval;
+use crate::eval::error::AssertArgsFailed;
+use crate::eval::error::AssertThisFailed;
+use crate::eval::error::CastFailed;
+use crate::eval::error::Error;
+use crate::eval::error::Result;
+use crate::eval::EvaluationContext;
+use crate::eval::scope::Mutability;
+use crate::eval::value;
+use crate::eval::value::ObjectRef;
+use crate::eval::value::SourcedValue;
+use crate::eval::value::Value;
+use crate::lock_deref;
+
+// Note that lists and objects that are returned by these functions have the
+// same mutability as the original object.
+
+#[allow(clippy::needless_pass_by_value)]
+pub fn len(
+    _context: &EvaluationContext,
+    _loc: (usize, usize),
+    this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("len", 0, &args)
+        .context(AssertArgsFailed)?;
+
+    let (props, _) = assert_this_object(this)?;
+
+    let n: i64 = lock_deref!(props).len().try_into()
+        .context(CastFailed)?;
+
+    Ok(value::new_int(n))
+}
+
+// `keys` returns the property names of the object, in sorted order.
+#[allow(clippy::needless_pass_by_value)]
+pub fn keys(
+    _context: &EvaluationContext,
+    _loc: (usize, usize),
+    this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("keys", 0, &args)
+        .context(AssertArgsFailed)?;
+
+    let (props, is_mutable) = assert_this_object(this)?;
+
+    let keys = lock_deref!(props).keys()
+        .map(|k| value::new_str_from_string(k.clone()))
+        .collect();
+
+    Ok(value::new_list_with_mutability(keys, is_mutable))
+}
+
+// `values` returns the property values of the object, in the order of their
+// names.
+#[allow(clippy::needless_pass_by_value)]
+pub fn values(
+    _context: &EvaluationContext,
+    _loc: (usize, usize),
+    this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("values", 0, &args)
+        .context(AssertArgsFailed)?;
+
+    let (props, is_mutable) = assert_this_object(this)?;
+
+    let values = lock_deref!(props).values().cloned().collect();
+
+    Ok(value::new_list_with_mutability(values, is_mutable))
+}
+
+// `entries` returns a list of `[name, value]` pairs for the properties of the
+// object, in the same order that they're iterated over by `for` loops.
+#[allow(clippy::needless_pass_by_value)]
+pub fn entries(
+    _context: &EvaluationContext,
+    _loc: (usize, usize),
+    this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("entries", 0, &args)
+        .context(AssertArgsFailed)?;
+
+    let this = fns::assert_this(this)
+        .context(AssertThisFailed)?;
+
+    let (_, is_mutable) = assert_object("this", &this)?;
+
+    let entries = eval::value_to_pairs(&this.v)?
+        .into_iter()
+        .map(|(k, v)| value::new_list(vec![k, v], &Mutability::Const))
+        .collect();
+
+    Ok(value::new_list_with_mutability(entries, is_mutable))
+}
+
+#[allow(clippy::needless_pass_by_value)]
+pub fn has(
+    _context: &EvaluationContext,
+    _loc: (usize, usize),
+    this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("has", 1, &args)
+        .context(AssertArgsFailed)?;
+
+    let (props, _) = assert_this_object(this)?;
+    let key = assert_key("key", &args[0])?;
+
+    let found = lock_deref!(props).contains_key(&key);
+
+    Ok(value::new_bool(found))
+}
+
+// `get` returns the value of the `key` property of the object, or `default` if
+// the object doesn't have a `key` property. `default` is optional, and
+// defaults to `null`.
+#[allow(clippy::needless_pass_by_value)]
+pub fn get(
+    _context: &EvaluationContext,
+    _loc: (usize, usize),
+    this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args_between("get", 1, 2, &args)
+        .context(AssertArgsFailed)?;
+
+    let (props, _) = assert_this_object(this)?;
+    let key = assert_key("key", &args[0])?;
+
+    let maybe_value = lock_deref!(props).get(&key).cloned();
+
+    match maybe_value {
+        Some(v) => Ok(v),
+        None => Ok(args.get(1).cloned().unwrap_or_else(value::new_null)),
+    }
+}
+
+// `remove` removes the `key` property from the object and returns its value.
+#[allow(clippy::needless_pass_by_value)]
+pub fn remove(
+    _context: &EvaluationContext,
+    _loc: (usize, usize),
+    this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("remove", 1, &args)
+        .context(AssertArgsFailed)?;
+
+    let (props, is_mutable) = assert_this_object(this)?;
+    let key = assert_key("key", &args[0])?;
+
+    if !is_mutable {
+        return Err(Error::ObjectNotMutable{assign_type: "remove".to_string()});
+    }
+
+    let maybe_value = props.try_lock().unwrap().remove(&key);
+
+    match maybe_value {
+        Some(v) => Ok(v),
+        None => Err(Error::Runtime{msg: format!(
+            "object doesn't contain property '{key}'",
+        )}),
+    }
+}
+
+// `merge` returns a new object with the properties of the object and of
+// `other`, where the properties of `other` take precedence.
+#[allow(clippy::needless_pass_by_value)]
+pub fn merge(
+    _context: &EvaluationContext,
+    _loc: (usize, usize),
+    this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("merge", 1, &args)
+        .context(AssertArgsFailed)?;
+
+    let (props, is_mutable) = assert_this_object(this)?;
+    let (other, _) = assert_object("other", &args[0])?;
+
+    let mut merged = lock_deref!(props).clone();
+    for (k, v) in &lock_deref!(other) {
+        merged.insert(k.clone(), v.clone());
+    }
+
+    Ok(value::new_val_ref_with_no_source(Value::Object{
+        props: Arc::new(Mutex::new(merged)),
+        is_mutable,
+    }))
+}
+
+fn assert_this_object(this: Option<SourcedValue>)
+    -> Result<(ObjectRef, bool)>
+{
+    let this = fns::assert_this(this)
+        .context(AssertThisFailed)?;
+
+    assert_object("this", &this)
+}
+
+fn assert_object(val_name: &str, v: &SourcedValue)
+    -> Result<(ObjectRef, bool)>
+{
+    match &v.v {
+        Value::Object{props, is_mutable} => Ok((props.clone(), *is_mutable)),
+        value => Err(Error::IncorrectType{
+            descr: format!("`{val_name}`"),
+            exp_type: "object".to_string(),
+            value: value.clone(),
+        }),
+    }
+}
+
+// `assert_key` returns the value of `v`, which must be a valid property name.
+fn assert_key(val_name: &str, v: &SourcedValue) -> Result<String> {
+    let s = fns::assert_raw_str(val_name, v)?;
+
+    match String::from_utf8(s) {
+        Ok(s) => Ok(s),
+        Err(source) => Err(Error::StringConstructionFailed{
+            source,
+            descr: format!("`{val_name}`"),
+        }),
+    }
+}
@@ -0,0 +1,440 @@
+// Copyright 2025-2026 Sean Kelleher. All rights reserved.
+// Use of this source code is governed by an MIT
+// licence that can be found in the LICENCE file.
+
+use snafu::ResultExt;
+
+use crate::builtins::fns;
+use crate::eval::error::AssertArgsFailed;
+use crate::eval::error::AssertStrFailed;
+use crate::eval::error::AssertThisFailed;
+use crate::eval::error::CastFailed;
+use crate::eval::error::Error;
+use crate::eval::error::Result;
+use crate::eval::EvaluationContext;
+use crate::eval::scope::Mutability;
+use crate::eval::value;
+use crate::eval::value::SourcedValue;
+use crate::eval::value::Str;
+
+// Note that string functions operate on the bytes of strings, so indices and
+// lengths are given in bytes, and case conversion only applies to ASCII
+// characters.
+
+#[allow(clippy::needless_pass_by_value)]
+pub fn len(
+    _context: &EvaluationContext,
+    _loc: (usize, usize),
+    this: Option<SourcedValue>,
+    vs: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("len", 0, &vs)
+        .context(AssertArgsFailed)?;
+
+    let this = fns::assert_this(this)
+        .context(AssertThisFailed)?;
+
+    let s = fns::assert_str("this", &this)
+        .context(AssertStrFailed)?;
+
+    let n: i64 = s.len().try_into()
+        .context(CastFailed)?;
+
+    Ok(value::new_int(n))
+}
+
+// `split` returns the substrings of the string that are separated by `sep`.
+#[allow(clippy::needless_pass_by_value)]
+pub fn split(
+    _context: &EvaluationContext,
+    _loc: (usize, usize),
+    this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("split", 1, &args)
+        .context(AssertArgsFailed)?;
+
+    let s = assert_this_str(this)?;
+    let sep = assert_non_empty_str("split", "sep", &args[0])?;
+
+    let mut parts = vec![];
+    let mut rest = &s[..];
+    while let Some(i) = find_bytes(rest, &sep) {
+        parts.push(value::new_str(rest[..i].to_vec()));
+        rest = &rest[i+sep.len()..];
+    }
+    parts.push(value::new_str(rest.to_vec()));
+
+    Ok(value::new_list(parts, &Mutability::Const))
+}
+
+// `lines` returns the lines of the string, without their line endings. A
+// final line ending doesn't start a new, empty line.
+#[allow(clippy::needless_pass_by_value)]
+pub fn lines(
+    _context: &EvaluationContext,
+    _loc: (usize, usize),
+    this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("lines", 0, &args)
+        .context(AssertArgsFailed)?;
+
+    let s = assert_this_str(this)?;
+
+    let mut lines: Vec<&[u8]> = s.split(|&c| c == b'\n').collect();
+    if s.is_empty() || s.ends_with(b"\n") {
+        lines.pop();
+    }
+
+    let lines = lines.into_iter()
+        .map(|line| {
+            let line = line.strip_suffix(b"\r").unwrap_or(line);
+
+            value::new_str(line.to_vec())
+        })
+        .collect();
+
+    Ok(value::new_list(lines, &Mutability::Const))
+}
+
+// `trim` returns the string with leading and trailing ASCII whitespace
+// removed.
+#[allow(clippy::needless_pass_by_value)]
+pub fn trim(
+    _context: &EvaluationContext,
+    _loc: (usize, usize),
+    this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("trim", 0, &args)
+        .context(AssertArgsFailed)?;
+
+    let s = assert_this_str(this)?;
+
+    Ok(value::new_str(s.trim_ascii().to_vec()))
+}
+
+#[allow(clippy::needless_pass_by_value)]
+pub fn trim_start(
+    _context: &EvaluationContext,
+    _loc: (usize, usize),
+    this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("trim_start", 0, &args)
+        .context(AssertArgsFailed)?;
+
+    let s = assert_this_str(this)?;
+
+    Ok(value::new_str(s.trim_ascii_start().to_vec()))
+}
+
+#[allow(clippy::needless_pass_by_value)]
+pub fn trim_end(
+    _context: &EvaluationContext,
+    _loc: (usize, usize),
+    this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("trim_end", 0, &args)
+        .context(AssertArgsFailed)?;
+
+    let s = assert_this_str(this)?;
+
+    Ok(value::new_str(s.trim_ascii_end().to_vec()))
+}
+
+// `replace` returns the string with every occurrence of `from` replaced with
+// `to`.
+#[allow(clippy::needless_pass_by_value)]
+pub fn replace(
+    _context: &EvaluationContext,
+    _loc: (usize, usize),
+    this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("replace", 2, &args)
+        .context(AssertArgsFailed)?;
+
+    let s = assert_this_str(this)?;
+    let from = assert_non_empty_str("replace", "from", &args[0])?;
+    let to = fns::assert_raw_str("to", &args[1])?;
+
+    let mut replaced = vec![];
+    let mut rest = &s[..];
+    while let Some(i) = find_bytes(rest, &from) {
+        replaced.extend_from_slice(&rest[..i]);
+        replaced.extend_from_slice(&to);
+        rest = &rest[i+from.len()..];
+    }
+    replaced.extend_from_slice(rest);
+
+    Ok(value::new_str(replaced))
+}
+
+#[allow(clippy::needless_pass_by_value)]
+pub fn contains(
+    _context: &EvaluationContext,
+    _loc: (usize, usize),
+    this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("contains", 1, &args)
+        .context(AssertArgsFailed)?;
+
+    let s = assert_this_str(this)?;
+    let sub = fns::assert_raw_str("sub", &args[0])?;
+
+    Ok(value::new_bool(find_bytes(&s, &sub).is_some()))
+}
+
+#[allow(clippy::needless_pass_by_value)]
+pub fn starts_with(
+    _context: &EvaluationContext,
+    _loc: (usize, usize),
+    this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("starts_with", 1, &args)
+        .context(AssertArgsFailed)?;
+
+    let s = assert_this_str(this)?;
+    let prefix = fns::assert_raw_str("prefix", &args[0])?;
+
+    Ok(value::new_bool(s.starts_with(&prefix)))
+}
+
+#[allow(clippy::needless_pass_by_value)]
+pub fn ends_with(
+    _context: &EvaluationContext,
+    _loc: (usize, usize),
+    this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("ends_with", 1, &args)
+        .context(AssertArgsFailed)?;
+
+    let s = assert_this_str(this)?;
+    let suffix = fns::assert_raw_str("suffix", &args[0])?;
+
+    Ok(value::new_bool(s.ends_with(&suffix)))
+}
+
+// `find` returns the index of the first occurrence of `sub` in the string, or
+// `-1` if the string doesn't contain `sub`.
+#[allow(clippy::needless_pass_by_value)]
+pub fn find(
+    _context: &EvaluationContext,
+    _loc: (usize, usize),
+    this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("find", 1, &args)
+        .context(AssertArgsFailed)?;
+
+    let s = assert_this_str(this)?;
+    let sub = fns::assert_raw_str("sub", &args[0])?;
+
+    let index =
+        match find_bytes(&s, &sub) {
+            Some(i) => i.try_into().context(CastFailed)?,
+            None => -1,
+        };
+
+    Ok(value::new_int(index))
+}
+
+#[allow(clippy::needless_pass_by_value)]
+pub fn upper(
+    _context: &EvaluationContext,
+    _loc: (usize, usize),
+    this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("upper", 0, &args)
+        .context(AssertArgsFailed)?;
+
+    let s = assert_this_str(this)?;
+
+    Ok(value::new_str(s.to_ascii_uppercase()))
+}
+
+#[allow(clippy::needless_pass_by_value)]
+pub fn lower(
+    _context: &EvaluationContext,
+    _loc: (usize, usize),
+    this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("lower", 0, &args)
+        .context(AssertArgsFailed)?;
+
+    let s = assert_this_str(this)?;
+
+    Ok(value::new_str(s.to_ascii_lowercase()))
+}
+
+// `repeat` returns the string repeated `n` times.
+#[allow(clippy::needless_pass_by_value)]
+pub fn repeat(
+    _context: &EvaluationContext,
+    _loc: (usize, usize),
+    this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    fns::assert_args("repeat", 1, &args)
+        .context(AssertArgsFailed)?;
+
+    let s = assert_this_str(this)?;
+    let n = assert_count("repeat", "n", &args[0])?;
+    assert_str_len("repeat", s.len().checked_mul(n))?;
+
+    Ok(value::new_str(s.repeat(n)))
+}
+
+// `pad_left` returns the string with `fill` added to its start until it's
+// `width` bytes long. `fill` defaults to a space, and must be a single byte.
+#[allow(clippy::needless_pass_by_value)]
+pub fn pad_left(
+    _context: &EvaluationContext,
+    _loc: (usize, usize),
+    this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    let (s, padding) = new_padding("pad_left", this, &args)?;
+
+    Ok(value::new_str([padding, s].concat()))
+}
+
+// `pad_right` returns the string with `fill` added to its end until it's
+// `width` bytes long. `fill` defaults to a space, and must be a single byte.
+#[allow(clippy::needless_pass_by_value)]
+pub fn pad_right(
+    _context: &EvaluationContext,
+    _loc: (usize, usize),
+    this: Option<SourcedValue>,
+    args: Vec<SourcedValue>,
+)
+    -> Result<SourcedValue>
+{
+    let (s, padding) = new_padding("pad_right", this, &args)?;
+
+    Ok(value::new_str([s, padding].concat()))
+}
+
+// `new_padding` returns the string that `pad_left` and `pad_right` are called
+// on, along with the padding that should be added to it.
+fn new_padding(
+    fn_name: &str,
+    this: Option<SourcedValue>,
+    args: &[SourcedValue],
+)
+    -> Result<(Str, Str)>
+{
+    fns::assert_args_between(fn_name, 1, 2, args)
+        .context(AssertArgsFailed)?;
+
+    let s = assert_this_str(this)?;
+    let width = assert_count(fn_name, "width", &args[0])?;
+    assert_str_len(fn_name, Some(width))?;
+
+    let mut fill = b' ';
+    if let Some(v) = args.get(1) {
+        fill =
+            match fns::assert_raw_str("fill", v)?[..] {
+                [c] => c,
+                _ => return Err(Error::BuiltinFuncErr{msg: format!(
+                    "`{fn_name}` requires `fill` to be a single character",
+                )}),
+            };
+    }
+
+    let padding = vec![fill; width.saturating_sub(s.len())];
+
+    Ok((s, padding))
+}
+
+fn assert_this_str(this: Option<SourcedValue>) -> Result<Str> {
+    let this = fns::assert_this(this)
+        .context(AssertThisFailed)?;
+
+    fns::assert_raw_str("this", &this)
+}
+
+fn assert_non_empty_str(fn_name: &str, val_name: &str, v: &SourcedValue)
+    -> Result<Str>
+{
+    let s = fns::assert_raw_str(val_name, v)?;
+    if s.is_empty() {
+        return Err(Error::BuiltinFuncErr{msg: format!(
+            "`{fn_name}` requires `{val_name}` to be non-empty",
+        )});
+    }
+
+    Ok(s)
+}
+
+// `assert_str_len` returns an error if `len`, the length of the string that
+// `fn_name` would create, is `None` or is greater than `value::MAX_STR_LEN`.
+fn assert_str_len(fn_name: &str, len: Option<usize>) -> Result<()> {
+    match len {
+        Some(len) if len <= value::MAX_STR_LEN => Ok(()),
+        _ => Err(Error::BuiltinFuncErr{msg: format!(
+            "`{fn_name}` can't create a string longer than {} bytes",
+            value::MAX_STR_LEN,
+        )}),
+    }
+}
+
+// `assert_count` returns the value of `v`, which must be a non-negative int.
+fn assert_count(fn_name: &str, val_name: &str, v: &SourcedValue)
+    -> Result<usize>
+{
+    let n = fns::assert_int(val_name, v)?;
+
+    match usize::try_from(n) {
+        Ok(n) => Ok(n),
+        Err(_) => Err(Error::BuiltinFuncErr{msg: format!(
+            "`{fn_name}` requires `{val_name}` to be non-negative (got {n})",
+        )}),
+    }
+}
+
+fn find_bytes(s: &[u8], sub: &[u8]) -> Option<usize> {
+    if sub.is_empty() {
+        return Some(0);
+    }
+
+    s.windows(sub.len()).position(|w| w == sub)
+}
@@ -4,6 +4,7 @@
 
 use std::collections::BTreeMap;
 use std::collections::HashSet;
+use std::sync::Arc;
 
 use snafu::ResultExt;
 
@@ -20,6 +21,7 @@ use super::scope::Mutability;
 use super::scope::ScopeStack;
 use crate::lock_deref;
 use crate::value;
+use value::EnumType;
 use value::ListRef;
 use value::ObjectRef;
 use value::SourcedValue;
@@ -58,6 +60,183 @@ pub enum BindType {
     ConstDeclaration,
     VarDeclaration,
     Assignment,
+    // `Match` declares the names of a `match` pattern as variables. Literals
+    // are allowed in `Match` bindings, and are expected to have been checked
+    // using `fits`.
+    Match,
+}
+
+// `fits` returns whether `rhs` has the shape of the pattern `lhs`, i.e.
+// whether binding `lhs` to `rhs` would succeed, where literals in `lhs` only
+// fit values that are equal to them. `fits` returns `true` for patterns that
+// can't be bound, so that the error is reported when `lhs` is bound.
+#[allow(clippy::too_many_lines)]
+pub fn fits(
+    context: &EvaluationContext,
+    scopes: &mut ScopeStack,
+    lhs: &Expr,
+    rhs: &Value,
+)
+    -> Result<bool>
+{
+    let (raw_lhs, _) = lhs;
+
+    match raw_lhs {
+        RawExpr::Null |
+        RawExpr::Bool{..} |
+        RawExpr::Int{..} |
+        RawExpr::Float{..} |
+        RawExpr::Str{..} => {
+            let v = eval::eval_expr(context, scopes, lhs)
+                .context(EvalExprFailed)?;
+
+            Ok(matches!(eval::eq(&v.v, rhs), Ok(true)))
+        },
+
+        RawExpr::List{items: lhs_items, collect, ..} => {
+            let rhs_items =
+                match rhs {
+                    Value::List{items, ..} => lock_deref!(items).clone(),
+                    _ => return Ok(false),
+                };
+
+            let lhs_len = lhs_items.len();
+            let rhs_len = rhs_items.len();
+            if *collect {
+                if lhs_len-1 > rhs_len {
+                    return Ok(false);
+                }
+            } else if lhs_len != rhs_len {
+                return Ok(false);
+            }
+
+            for (i, ListItem{expr: lhs, ..}) in lhs_items.iter().enumerate() {
+                let rhs =
+                    if *collect && i == lhs_len-1 {
+                        value::new_list(
+                            rhs_items[i ..].to_vec(),
+                            &Mutability::Const,
+                        )
+                    } else {
+                        rhs_items[i].clone()
+                    };
+
+                if !fits(context, scopes, lhs, &rhs.v)? {
+                    return Ok(false);
+                }
+            }
+
+            Ok(true)
+        },
+
+        RawExpr::Object{props: lhs_props, ..} => {
+            let rhs_props =
+                match rhs {
+                    Value::Object{props, ..} => lock_deref!(props).clone(),
+                    _ => return Ok(false),
+                };
+
+            for prop_item in lhs_props {
+                let (prop_name, lhs) =
+                    match prop_item {
+                        PropItem::Single{expr, collect: false, ..} => {
+                            if let (RawExpr::Var{name}, _) = expr {
+                                (name.clone(), expr)
+                            } else {
+                                continue;
+                            }
+                        },
+                        PropItem::Single{..} => {
+                            continue;
+                        },
+                        PropItem::Pair{name, value: lhs} => {
+                            let descr = "property";
+                            let prop_name =
+                                eval::eval_expr_to_str(
+                                    context,
+                                    scopes,
+                                    descr,
+                                    name,
+                                )
+                                    .context(EvalObjectIndexFailed)?;
+
+                            (prop_name, lhs)
+                        },
+                    };
+
+                if prop_name == "_" {
+                    continue;
+                }
+
+                let fits_prop =
+                    match rhs_props.get(&prop_name) {
+                        Some(v) => fits(context, scopes, lhs, &v.v)?,
+                        None => false,
+                    };
+
+                if !fits_prop {
+                    return Ok(false);
+                }
+            }
+
+            Ok(true)
+        },
+
+        RawExpr::Prop{expr, name, type_prop: false} => {
+            match_eval_expr!((context, scopes, expr) {
+                Value::Enum(typ) => {
+                    match value::variant_index(&typ, name) {
+                        Some(variant) => Ok(matches!(
+                            rhs,
+                            Value::Variant{typ: rhs_typ, variant: v, ..}
+                                if Arc::ptr_eq(&typ, rhs_typ) && *v == variant
+                        )),
+                        None => Ok(true),
+                    }
+                },
+
+                _ => Ok(true),
+            })
+        },
+
+        RawExpr::Call{func, args} => {
+            match_eval_expr!((context, scopes, func) {
+                Value::EnumConstructor{typ, variant} => {
+                    let payload =
+                        match rhs {
+                            Value::Variant{
+                                typ: rhs_typ,
+                                variant: rhs_variant,
+                                payload,
+                            } if
+                                Arc::ptr_eq(&typ, rhs_typ) &&
+                                    *rhs_variant == variant =>
+                            {
+                                payload
+                            },
+
+                            _ => {
+                                return Ok(false);
+                            },
+                        };
+
+                    for (ListItem{expr: lhs, ..}, rhs) in
+                        args.iter().zip(payload)
+                    {
+                        if !fits(context, scopes, lhs, &rhs.v)? {
+                            return Ok(false);
+                        }
+                    }
+
+                    Ok(true)
+                },
+
+                _ => Ok(true),
+            })
+        },
+
+        _ => Ok(true),
+    }
 }
 
 // `bind_next` performs a bind, but returns an error if a name that's in
@@ -246,6 +425,32 @@ pub fn bind_next(
                     Ok(())
                 },
 
+                // A property of an enum is treated as a pattern that only
+                // matches the named variant, which must not have a payload.
+                Value::Enum(typ) => {
+                    if op.is_some() {
+                        return new_loc_err(Error::OpOnVariantDestructure);
+                    }
+
+                    let variant =
+                        match value::variant_index(&typ, name) {
+                            Some(variant) => variant,
+                            None => return new_loc_err(
+                                eval::new_no_such_variant(&typ, name),
+                            ),
+                        };
+
+                    bind_variant(
+                        context,
+                        scopes,
+                        names_in_binding,
+                        (&typ, variant, &[]),
+                        loc,
+                        rhs,
+                        bind_type,
+                    )
+                },
+
                 value => {
                     new_loc_err(Error::PropAccessOnNonObject{value})
                 },
@@ -299,12 +504,23 @@ pub fn bind_next(
             }
         },
 
+        // Literals don't bind any names, and `fits` checks whether they're
+        // equal to the value that they're matched against.
+        RawExpr::Null |
+        RawExpr::Bool{..} |
+        RawExpr::Int{..} |
+        RawExpr::Float{..} |
+        RawExpr::Str{..} if bind_type == BindType::Match =>
+            Ok(()),
+
         RawExpr::Null =>
             new_invalid_bind_error("`null`"),
         RawExpr::Bool{..} =>
             new_invalid_bind_error("a boolean literal"),
         RawExpr::Int{..} =>
             new_invalid_bind_error("an integer literal"),
+        RawExpr::Float{..} =>
+            new_invalid_bind_error("a float literal"),
         RawExpr::Str{..} =>
             new_invalid_bind_error("a string literal"),
         RawExpr::UnaryOp{..} =>
@@ -315,10 +531,37 @@ pub fn bind_next(
             new_invalid_bind_error("a range operation"),
         RawExpr::Func{..} =>
             new_invalid_bind_error("an anonymous function"),
-        RawExpr::Call{..} =>
-            new_invalid_bind_error("a function call"),
+        RawExpr::Call{func, args} => {
+            match_eval_expr!((context, scopes, func) {
+                Value::EnumConstructor{typ, variant} => {
+                    if op.is_some() {
+                        return new_loc_err(Error::OpOnVariantDestructure);
+                    }
+
+                    bind_variant(
+                        context,
+                        scopes,
+                        names_in_binding,
+                        (&typ, variant, args),
+                        loc,
+                        rhs,
+                        bind_type,
+                    )
+                },
+
+                _ => {
+                    new_invalid_bind_error("a function call")
+                },
+            })
+        },
         RawExpr::CatchAsBool{..} =>
             new_invalid_bind_error("a boolean catch"),
+        RawExpr::Command{..} =>
+            new_invalid_bind_error("a command"),
+        RawExpr::Pipeline{..} =>
+            new_invalid_bind_error("a pipeline"),
+        RawExpr::Import{..} =>
+            new_invalid_bind_error("an import"),
     }
 }
 
@@ -387,7 +630,8 @@ fn bind_next_name(
 
     match bind_type {
         BindType::ConstDeclaration |
-        BindType::VarDeclaration => {
+        BindType::VarDeclaration |
+        BindType::Match => {
             if op.is_some() {
                 return new_loc_error(Error::Dev{
                     msg: "operation-assignment on declaration".to_string(),
@@ -669,6 +913,68 @@ fn bind_object_prop(
     Ok(())
 }
 
+// `bind_variant` binds the payload of `rhs` to `args`, if `rhs` is the
+// `variant` of `typ`.
+fn bind_variant(
+    context: &EvaluationContext,
+    scopes: &mut ScopeStack,
+    names_in_binding: &mut HashSet<String>,
+    raw_lhs: (&Arc<EnumType>, usize, &[ListItem]),
+    lhs_loc: &Location,
+    rhs: SourcedValue,
+    bind_type: BindType,
+)
+    -> Result<()>
+{
+    let (typ, variant, args) = raw_lhs;
+
+    let new_loc_err = |source| {
+        let (line, col) = lhs_loc;
+
+        Err(Error::AtLoc{source: Box::new(source), line: *line, col: *col})
+    };
+
+    let variant_name = value::variant_name(typ, variant);
+
+    let need = typ.variants[variant].fields.len();
+    if args.len() != need {
+        return new_loc_err(Error::VariantDestructureItemMismatch{
+            variant: variant_name,
+            need,
+            got: args.len(),
+        });
+    }
+
+    let payload =
+        match rhs.v {
+            Value::Variant{typ: rhs_typ, variant: rhs_variant, payload}
+                if Arc::ptr_eq(typ, &rhs_typ) && rhs_variant == variant =>
+            {
+                payload
+            },
+
+            value => {
+                return new_loc_err(Error::VariantMismatch{
+                    variant: variant_name,
+                    value,
+                });
+            },
+        };
+
+    for (i, (ListItem{expr: lhs, is_spread}, rhs)) in
+        args.iter().zip(payload).enumerate()
+    {
+        if *is_spread {
+            return new_loc_err(Error::SpreadInVariantDestructure{index: i});
+        }
+
+        bind_next(context, scopes, names_in_binding, lhs, rhs, None, bind_type)
+            .context(BindVariantItemFailed)?;
+    }
+
+    Ok(())
+}
+
 fn bind_list(
     context: &EvaluationContext,
     scopes: &mut ScopeStack,
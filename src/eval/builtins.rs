@@ -5,8 +5,7 @@
 pub use super::value::ObjectRef;
 
 pub struct Builtins {
-    // TODO `std` will be populated by the standard library.
-    #[allow(dead_code)]
+    // `std` is bound to the global `std` variable by `eval_prog`.
     pub std: ObjectRef,
     pub type_functions: TypeFunctions,
 }
@@ -14,8 +13,10 @@ pub struct Builtins {
 pub struct TypeFunctions {
     pub bools: ObjectRef,
     pub ints: ObjectRef,
+    pub floats: ObjectRef,
     pub strs: ObjectRef,
     pub lists: ObjectRef,
     pub objects: ObjectRef,
     pub funcs: ObjectRef,
+    pub enums: ObjectRef,
 }
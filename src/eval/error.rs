@@ -3,12 +3,14 @@
 // licence that can be found in the LICENCE file.
 
 use std::num::TryFromIntError;
+use std::path::PathBuf;
 use std::string::FromUtf8Error;
 
 use snafu::prelude::*;
 
 use crate::ast::UnaryOp;
 use crate::ast::BinaryOp;
+use crate::eval::value;
 use crate::eval::Value;
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -174,6 +176,29 @@ pub enum Error {
         index,
     ))]
     SpreadInListDestructure{index: usize},
+    #[snafu(display(
+        "expected a '{}' value, got '{}'",
+        variant,
+        render_variant(value),
+    ))]
+    VariantMismatch{variant: String, value: Value},
+    #[snafu(display(
+        "no `match` arm fits the value, got '{}'",
+        render_variant(value),
+    ))]
+    NoMatchingArm{value: Value},
+    #[snafu(display(
+        "'{}' has {} payload item(s), got {} variable name(s)",
+        variant,
+        need,
+        got,
+    ))]
+    VariantDestructureItemMismatch{variant: String, need: usize, got: usize},
+    #[snafu(display(
+        "cannot use spread operator (at index {}) of variant destructure",
+        index,
+    ))]
+    SpreadInVariantDestructure{index: usize},
     #[snafu(display(
         "cannot bind {} item(s) to {} index(s)",
         rhs_len,
@@ -194,12 +219,15 @@ pub enum Error {
     ))]
     PropAccessOnNonObject{value: Value},
     #[snafu(display(
-        "interpolated values can only be strings, got '{}'",
+        "'{}' values can only be interpolated using the 'json' format spec",
         render_type(value),
     ))]
-    InterpolatedValueNotString{value: Value},
-    #[snafu(display("couldn't parse interpolation slot: {}", source_str))]
-    InterpolateStringParseFailed{source_str: String},
+    InterpolatedValueNotScalar{value: Value},
+    #[snafu(display(
+        "the '0' format flag can only be used with 'int's, got '{}'",
+        render_type(value),
+    ))]
+    ZeroPadNonInt{value: Value},
     #[snafu(display("'{}' is not defined", name))]
     OpOnUndefinedIndex{name: String},
     #[snafu(display("'{}' is not defined", name))]
@@ -210,17 +238,40 @@ pub enum Error {
     OpOnObjectDestructure,
     #[snafu(display("cannot perform this operation on an list destructure"))]
     OpOnListDestructure,
+    #[snafu(display("cannot perform this operation on a variant destructure"))]
+    OpOnVariantDestructure,
     #[snafu(display("'{}' is already declared at [{}:{}]", name, line, col))]
     DupParamName{name: String, line: usize, col: usize},
+    #[snafu(display("'{}' is already declared at [{}:{}]", name, line, col))]
+    DupVariantName{name: String, line: usize, col: usize},
+    #[snafu(display(
+        "'{}' is declared multiple times in the variant '{}'",
+        field,
+        variant,
+    ))]
+    DupVariantField{variant: String, field: String},
     #[snafu(display("can't use spread operator in parameter list"))]
     PropSpreadInParamList,
     #[snafu(display("can't use spread operator in parameter list"))]
     ItemSpreadInParamList,
+    #[snafu(display("commands must contain at least one argument"))]
+    EmptyCommand,
+    #[snafu(display("only commands can be used as pipeline stages"))]
+    PipelineStageNotCommand,
+    #[snafu(display("import cycle detected: {}", cycle))]
+    ImportCycle{cycle: String},
+    #[snafu(display("{}", msg))]
+    ParseModuleFailed{msg: String},
 
     #[snafu(display("{}", msg))]
     BuiltinFuncErr{msg: String},
     #[snafu(display("{}", msg))]
     Runtime{msg: String},
+    // `Exit` doesn't represent a failure, but is returned by `exit` so that
+    // evaluation unwinds through all active function calls before the process
+    // exits.
+    #[snafu(display("exited with code {}", code))]
+    Exit{code: i32},
 
     #[snafu(display("dev error: {}", msg))]
     Dev{msg: String},
@@ -259,6 +310,10 @@ pub enum Error {
         #[snafu(source(from(Error, Box::new)))]
         source: Box<Error>,
     },
+    BindVariantItemFailed{
+        #[snafu(source(from(Error, Box::new)))]
+        source: Box<Error>,
+    },
     BindNextFailed{
         #[snafu(source(from(Error, Box::new)))]
         source: Box<Error>,
@@ -275,10 +330,6 @@ pub enum Error {
         #[snafu(source(from(Error, Box::new)))]
         source: Box<Error>,
     },
-    EvalStmtsFailed{
-        #[snafu(source(from(Error, Box::new)))]
-        source: Box<Error>,
-    },
     EvalDeclarationRhsFailed{
         #[snafu(source(from(Error, Box::new)))]
         source: Box<Error>,
@@ -311,6 +362,26 @@ pub enum Error {
         #[snafu(source(from(Error, Box::new)))]
         source: Box<Error>,
     },
+    EvalMatchValueFailed{
+        #[snafu(source(from(Error, Box::new)))]
+        source: Box<Error>,
+    },
+    CheckMatchPatternFailed{
+        #[snafu(source(from(Error, Box::new)))]
+        source: Box<Error>,
+    },
+    BindMatchPatternFailed{
+        #[snafu(source(from(Error, Box::new)))]
+        source: Box<Error>,
+    },
+    EvalMatchGuardFailed{
+        #[snafu(source(from(Error, Box::new)))]
+        source: Box<Error>,
+    },
+    EvalMatchStatementsFailed{
+        #[snafu(source(from(Error, Box::new)))]
+        source: Box<Error>,
+    },
     EvalWhileConditionFailed{
         #[snafu(source(from(Error, Box::new)))]
         source: Box<Error>,
@@ -339,6 +410,14 @@ pub enum Error {
         #[snafu(source(from(Error, Box::new)))]
         source: Box<Error>,
     },
+    ValidateVariantsFailed{
+        #[snafu(source(from(Error, Box::new)))]
+        source: Box<Error>,
+    },
+    DeclareEnumFailed{
+        #[snafu(source(from(Error, Box::new)))]
+        source: Box<Error>,
+    },
     EvalReturnExprFailed{
         #[snafu(source(from(Error, Box::new)))]
         source: Box<Error>,
@@ -470,16 +549,34 @@ pub enum Error {
         func_name: Option<String>,
         call_loc: (usize, usize),
     },
+    // `func_path` is the path of the script that the function was defined in.
     EvalFuncCallFailed{
         #[snafu(source(from(Error, Box::new)))]
         source: Box<Error>,
         func_name: Option<String>,
+        func_path: PathBuf,
         call_loc: (usize, usize),
     },
     EvalCatchAsBoolFailed{
         #[snafu(source(from(Error, Box::new)))]
         source: Box<Error>,
     },
+    // `path` is the path of the imported module, relative to the directory
+    // that the main script was run from.
+    EvalImportFailed{
+        #[snafu(source(from(Error, Box::new)))]
+        source: Box<Error>,
+        path: PathBuf,
+        import_loc: (usize, usize),
+    },
+    EvalCommandFailed{
+        #[snafu(source(from(Error, Box::new)))]
+        source: Box<Error>,
+    },
+    EvalPipelineStageFailed{
+        #[snafu(source(from(Error, Box::new)))]
+        source: Box<Error>,
+    },
     EvalExprFailed{
         #[snafu(source(from(Error, Box::new)))]
         source: Box<Error>,
@@ -521,12 +618,18 @@ pub fn render_type(v: &Value) -> String {
 
             Value::Bool(_) => "bool",
             Value::Int(_) => "int",
+            Value::Float(_) => "float",
             Value::Str(_) => "string",
 
             Value::List{..} => "list",
             Value::Object{..} => "object",
 
-            Value::BuiltinFunc{..} | Value::Func{..} => "func",
+            Value::BuiltinFunc{..} |
+            Value::Func{..} |
+            Value::EnumConstructor{..} => "func",
+
+            Value::Enum(_) => "enum",
+            Value::Variant{typ, ..} => return typ.name.clone(),
         };
 
     s.to_string()
@@ -536,6 +639,7 @@ fn unary_op_symbol(op: &UnaryOp) -> String {
     let s =
         match op {
             UnaryOp::Not => "!",
+            UnaryOp::BitNot => "~",
         };
 
     s.to_string()
@@ -549,6 +653,13 @@ pub fn bin_op_symbol(op: &BinaryOp) -> String {
             BinaryOp::Mul => "*",
             BinaryOp::Div => "/",
             BinaryOp::Mod => "%",
+            BinaryOp::Pow => "**",
+
+            BinaryOp::BitAnd => "&",
+            BinaryOp::BitOr => "|",
+            BinaryOp::BitXor => "^",
+            BinaryOp::Shl => "<<",
+            BinaryOp::Shr => ">>",
 
             BinaryOp::And => "&&",
             BinaryOp::Or => "||",
@@ -566,3 +677,13 @@ pub fn bin_op_symbol(op: &BinaryOp) -> String {
 
     s.to_string()
 }
+
+// `render_variant` returns the qualified name of the variant of `v` if `v` is
+// an enum value, and the type of `v` otherwise.
+fn render_variant(v: &Value) -> String {
+    match v {
+        Value::Variant{typ, variant, ..} =>
+            value::variant_name(typ, *variant),
+        v => render_type(v),
+    }
+}
@@ -0,0 +1,90 @@
+// Copyright 2026 Sean Kelleher. All rights reserved.
+// Use of this source code is governed by an MIT
+// licence that can be found in the LICENCE file.
+
+use super::error::Error;
+use super::error::Result;
+use super::json;
+use super::value::SourcedValue;
+use super::value::Value;
+use crate::ast::Align;
+use crate::ast::FormatSpec;
+use crate::builtins::fns;
+
+// `format` returns the rendering of `v` for an interpolation slot with the
+// format spec `spec`. Values other than `null`, bools, numbers, strings and
+// enum values can only be rendered using the `json` format spec.
+pub fn format(v: &SourcedValue, spec: Option<&FormatSpec>) -> Result<String> {
+    let (fill, align, zero, width) =
+        match spec {
+            Some(FormatSpec::Json) => {
+                return json::stringify(&v.v, false);
+            },
+            Some(FormatSpec::Pad{fill, align, zero, width}) => {
+                (*fill, align.as_ref(), *zero, *width)
+            },
+            None => {
+                (' ', None, false, 0)
+            },
+        };
+
+    let s =
+        match &v.v {
+            Value::Str(raw_str) => {
+                match String::from_utf8(raw_str.clone()) {
+                    Ok(s) => s,
+                    Err(source) => return Err(Error::StringConstructionFailed{
+                        source,
+                        descr: "interpolated slot".to_string(),
+                    }),
+                }
+            },
+            Value::Null |
+            Value::Bool(_) |
+            Value::Int(_) |
+            Value::Float(_) |
+            Value::Variant{..} => {
+                fns::render(v)?
+            },
+            value => {
+                return Err(Error::InterpolatedValueNotScalar{
+                    value: value.clone(),
+                });
+            },
+        };
+
+    let len = s.chars().count();
+    if len >= width {
+        return Ok(s);
+    }
+    let padding = width - len;
+
+    if zero {
+        if let Value::Int(n) = v.v {
+            let sign = if n < 0 { "-" } else { "" };
+            let digits = n.unsigned_abs().to_string();
+
+            return Ok(format!("{sign}{}{digits}", "0".repeat(padding)));
+        }
+
+        return Err(Error::ZeroPadNonInt{value: v.v.clone()});
+    }
+
+    let default_align =
+        if let Value::Int(_) | Value::Float(_) = v.v {
+            &Align::Right
+        } else {
+            &Align::Left
+        };
+
+    let (left, right) =
+        match align.unwrap_or(default_align) {
+            Align::Left => (0, padding),
+            Align::Right => (padding, 0),
+            Align::Center => (padding / 2, padding - padding / 2),
+        };
+
+    let pad = |n| fill.to_string().repeat(n);
+
+    Ok(format!("{}{s}{}", pad(left), pad(right)))
+}
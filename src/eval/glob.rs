@@ -0,0 +1,309 @@
+// Copyright 2026 Sean Kelleher. All rights reserved.
+// Use of this source code is governed by an MIT
+// licence that can be found in the LICENCE file.
+
+use std::collections::BTreeSet;
+use std::ffi::OsStr;
+use std::fs;
+use std::os::unix::ffi::OsStrExt;
+use std::path::Path;
+
+use super::value::Str;
+
+// `glob` returns the paths that match `pattern`, in sorted order. Relative
+// patterns are matched against the entries of `dir`, and the paths that are
+// returned are relative to `dir`.
+//
+// `pattern` supports `*`, `?` and `[...]` within path components, `**` as a
+// path component that matches any number of directories, and `{a,b}` for
+// alternation. As in most shells, wildcards don't match names that start with
+// `.` unless the pattern component also starts with `.`, and errors that occur
+// while reading directories are ignored.
+pub fn glob(dir: &Path, pattern: &[u8]) -> Vec<Str> {
+    let mut paths = BTreeSet::new();
+
+    for pat in expand_braces(pattern) {
+        let comps: Vec<&[u8]> = pat
+            .split(|&c| c == b'/')
+            .filter(|comp| !comp.is_empty())
+            .collect();
+
+        if pat.starts_with(b"/") {
+            match_comps(Path::new("/"), b"/".to_vec(), &comps, &mut paths);
+        } else {
+            match_comps(dir, vec![], &comps, &mut paths);
+        }
+    }
+
+    paths.into_iter().collect()
+}
+
+// `expand_braces` returns the patterns that result from expanding each
+// `{a,b}` group in `pattern`. Groups that aren't closed, or that don't contain
+// a `,`, are left as-is.
+fn expand_braces(pattern: &[u8]) -> Vec<Str> {
+    let mut i = 0;
+    while i < pattern.len() {
+        match pattern[i] {
+            b'\\' => {
+                i += 2;
+            },
+            b'{' => {
+                if let Some((alts, end)) = split_brace_group(pattern, i) {
+                    let prefix = &pattern[..i];
+                    let suffix = &pattern[end+1..];
+
+                    return alts.iter()
+                        .flat_map(|alt| {
+                            expand_braces(&[prefix, alt, suffix].concat())
+                        })
+                        .collect();
+                }
+                i += 1;
+            },
+            _ => {
+                i += 1;
+            },
+        }
+    }
+
+    vec![pattern.to_vec()]
+}
+
+// `split_brace_group` returns the alternatives of the brace group that starts
+// at `pattern[open]`, along with the index of the `}` that closes the group.
+fn split_brace_group(pattern: &[u8], open: usize)
+    -> Option<(Vec<&[u8]>, usize)>
+{
+    let mut alts = vec![];
+    let mut depth = 0;
+    let mut start = open + 1;
+    let mut i = open + 1;
+    while i < pattern.len() {
+        match pattern[i] {
+            b'\\' => {
+                i += 1;
+            },
+            b'{' => {
+                depth += 1;
+            },
+            b'}' if depth > 0 => {
+                depth -= 1;
+            },
+            b'}' => {
+                alts.push(&pattern[start..i]);
+                if alts.len() < 2 {
+                    return None;
+                }
+
+                return Some((alts, i));
+            },
+            b',' if depth == 0 => {
+                alts.push(&pattern[start..i]);
+                start = i + 1;
+            },
+            _ => {},
+        }
+        i += 1;
+    }
+
+    None
+}
+
+// `match_comps` adds the paths under `dir` that match `comps` to `paths`,
+// where `prefix` is the path of `dir` that's used in the returned paths.
+fn match_comps(
+    dir: &Path,
+    prefix: Str,
+    comps: &[&[u8]],
+    paths: &mut BTreeSet<Str>,
+) {
+    let (comp, rest) =
+        match comps.split_first() {
+            Some(v) => v,
+            None => {
+                if !prefix.is_empty() {
+                    paths.insert(prefix);
+                }
+
+                return;
+            },
+        };
+
+    if *comp == b"**" {
+        match_globstar(dir, &prefix, rest, paths);
+    } else if is_literal(comp) {
+        let path = dir.join(OsStr::from_bytes(comp));
+        if fs::symlink_metadata(&path).is_ok() {
+            match_comps(&path, join_prefix(&prefix, comp), rest, paths);
+        }
+    } else {
+        for (name, _) in read_dir_entries(dir) {
+            if match_name(comp, &name) {
+                let path = dir.join(OsStr::from_bytes(&name));
+                match_comps(&path, join_prefix(&prefix, &name), rest, paths);
+            }
+        }
+    }
+}
+
+// `match_globstar` adds the paths under `dir`, and under each of its
+// subdirectories, that match `comps` to `paths`. Symbolic links to directories
+// aren't followed, to avoid cycles.
+fn match_globstar(
+    dir: &Path,
+    prefix: &[u8],
+    comps: &[&[u8]],
+    paths: &mut BTreeSet<Str>,
+) {
+    match_comps(dir, prefix.to_vec(), comps, paths);
+
+    for (name, is_dir) in read_dir_entries(dir) {
+        if name.starts_with(b".") {
+            continue;
+        }
+
+        let sub_prefix = join_prefix(prefix, &name);
+        if is_dir {
+            let path = dir.join(OsStr::from_bytes(&name));
+            match_globstar(&path, &sub_prefix, comps, paths);
+        } else if comps.is_empty() {
+            paths.insert(sub_prefix);
+        }
+    }
+}
+
+// `read_dir_entries` returns the names of the entries of `dir`, along with
+// whether each entry is a directory.
+fn read_dir_entries(dir: &Path) -> Vec<(Str, bool)> {
+    let entries =
+        match fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(_) => return vec![],
+        };
+
+    entries
+        .filter_map(Result::ok)
+        .map(|entry| {
+            let is_dir = entry.file_type().is_ok_and(|t| t.is_dir());
+
+            (entry.file_name().as_bytes().to_vec(), is_dir)
+        })
+        .collect()
+}
+
+fn join_prefix(prefix: &[u8], name: &[u8]) -> Str {
+    if prefix.is_empty() {
+        name.to_vec()
+    } else if prefix.ends_with(b"/") {
+        [prefix, name].concat()
+    } else {
+        [prefix, b"/", name].concat()
+    }
+}
+
+fn is_literal(comp: &[u8]) -> bool {
+    !comp.iter().any(|c| matches!(c, b'*' | b'?' | b'[' | b'\\'))
+}
+
+fn match_name(pattern: &[u8], name: &[u8]) -> bool {
+    if name.starts_with(b".") && !pattern.starts_with(b".") {
+        return false;
+    }
+
+    match_pattern(pattern, name)
+}
+
+// `match_pattern` returns whether `name` matches `pattern`. When a character
+// of `name` doesn't match, only the most recent `*` is retried, with one more
+// character matched by it, because a match found by retrying an earlier `*`
+// can also be found by retrying the most recent one. This keeps matching
+// linear in the length of `pattern` for each character of `name`.
+fn match_pattern(pattern: &[u8], name: &[u8]) -> bool {
+    let mut p = 0;
+    let mut n = 0;
+    // `star` is the index in `pattern` after the most recent `*`, and the
+    // index in `name` of the first character that isn't matched by the `*`.
+    let mut star = None;
+
+    while n < name.len() {
+        if pattern.get(p) == Some(&b'*') {
+            p += 1;
+            star = Some((p, n));
+        } else if let Some(len) = match_char(&pattern[p..], name[n]) {
+            p += len;
+            n += 1;
+        } else if let Some((star_p, star_n)) = star {
+            p = star_p;
+            n = star_n + 1;
+            star = Some((star_p, n));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == b'*')
+}
+
+// `match_char` returns the length of the pattern item at the start of
+// `pattern` if it matches `c`, where the item isn't a `*`.
+fn match_char(pattern: &[u8], c: u8) -> Option<usize> {
+    let (p, pattern_rest) = pattern.split_first()?;
+
+    match p {
+        b'?' => {
+            Some(1)
+        },
+        b'[' => {
+            match match_class(pattern_rest, c) {
+                Some((matched, after)) => {
+                    matched.then_some(pattern.len() - after.len())
+                },
+                // We treat `[` as a literal character if it's not closed.
+                None => {
+                    (c == b'[').then_some(1)
+                },
+            }
+        },
+        b'\\' if !pattern_rest.is_empty() => {
+            (c == pattern_rest[0]).then_some(2)
+        },
+        _ => {
+            (c == *p).then_some(1)
+        },
+    }
+}
+
+// `match_class` returns whether `c` matches the character class at the start
+// of `class`, which follows an opening `[`, along with the remainder of the
+// pattern after the closing `]`. It returns `None` if the class isn't closed.
+fn match_class(class: &[u8], c: u8) -> Option<(bool, &[u8])> {
+    let (negated, mut i) =
+        match class.first() {
+            Some(b'!' | b'^') => (true, 1),
+            _ => (false, 0),
+        };
+
+    let mut matched = false;
+    let start = i;
+    loop {
+        let lo = *class.get(i)?;
+
+        // A `]` at the start of the class is treated as a literal character.
+        if lo == b']' && i > start {
+            return Some((matched != negated, &class[i+1..]));
+        }
+
+        let is_range =
+            class.get(i+1) == Some(&b'-') &&
+                class.get(i+2).is_some_and(|&hi| hi != b']');
+
+        if is_range {
+            matched |= (lo..=class[i+2]).contains(&c);
+            i += 3;
+        } else {
+            matched |= lo == c;
+            i += 1;
+        }
+    }
+}
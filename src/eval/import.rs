@@ -0,0 +1,172 @@
+// Copyright 2026 Sean Kelleher. All rights reserved.
+// Use of this source code is governed by an MIT
+// licence that can be found in the LICENCE file.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use snafu::ResultExt;
+
+use super::error::Error;
+use super::error::EvalImportFailed;
+use super::error::Result;
+use super::scope::Mutability;
+use super::value;
+use super::value::SourcedValue;
+use super::EvaluationContext;
+use crate::lexer::Lexer;
+use crate::parser::ProgParser;
+
+// `Modules` tracks the modules that have been imported during a run, so that
+// each module is only evaluated once, and so that import cycles can be
+// detected.
+pub struct Modules {
+    // `root_dir` is the directory that the main script was run from, which
+    // module paths are rendered relative to.
+    root_dir: PathBuf,
+    loaded: HashMap<PathBuf, SourcedValue>,
+    // `loading` contains the chain of modules that are currently being
+    // evaluated, where each module is being imported by the module before it.
+    loading: Vec<PathBuf>,
+}
+
+impl Modules {
+    // `main_script_path` should be `None` if the main script wasn't read from
+    // a file.
+    pub fn new(root_dir: PathBuf, main_script_path: Option<PathBuf>)
+        -> Modules
+    {
+        Modules{
+            root_dir,
+            loaded: HashMap::new(),
+            loading: main_script_path.into_iter().collect(),
+        }
+    }
+
+    fn render_path(&self, path: &Path) -> PathBuf {
+        match path.strip_prefix(&self.root_dir) {
+            Ok(p) => p.to_path_buf(),
+            Err(_) => path.to_path_buf(),
+        }
+    }
+}
+
+// `import` evaluates the module at `raw_path`, relative to the directory of
+// the script that the import is written in, and returns its top-level
+// declarations as an object. The module is only evaluated the first time that
+// it's imported, and later imports return the same object.
+pub fn import(
+    context: &EvaluationContext,
+    raw_path: &str,
+    loc: (usize, usize),
+)
+    -> Result<SourcedValue>
+{
+    let (line, col) = loc;
+    let new_loc_err = |source| {
+        Err(Error::AtLoc{source: Box::new(source), line, col})
+    };
+
+    let path =
+        match context.cur_script_dir.join(raw_path).canonicalize() {
+            Ok(p) => p,
+            Err(e) => return new_loc_err(Error::Runtime{msg: format!(
+                "couldn't import '{raw_path}': {e}",
+            )}),
+        };
+
+    let mut modules = context.modules.lock().unwrap();
+
+    if let Some(v) = modules.loaded.get(&path) {
+        return Ok(v.clone());
+    }
+
+    if let Some(i) = modules.loading.iter().position(|p| *p == path) {
+        let cycle =
+            modules.loading[i..]
+                .iter()
+                .chain([&path])
+                .map(|p| modules.render_path(p).display().to_string())
+                .collect::<Vec<String>>()
+                .join(" -> ");
+
+        return new_loc_err(Error::ImportCycle{cycle});
+    }
+
+    modules.loading.push(path.clone());
+    let rendered_path = modules.render_path(&path);
+
+    // We release the lock before evaluating the module, because the module
+    // can import other modules.
+    drop(modules);
+
+    let result = eval_module(context, &path, &rendered_path)
+        .context(EvalImportFailed{path: rendered_path, import_loc: loc});
+
+    let mut modules = context.modules.lock().unwrap();
+    modules.loading.pop();
+    if let Ok(v) = &result {
+        modules.loaded.insert(path, v.clone());
+    }
+
+    result
+}
+
+fn eval_module(
+    context: &EvaluationContext,
+    path: &Path,
+    rendered_path: &Path,
+)
+    -> Result<SourcedValue>
+{
+    let src =
+        match fs::read_to_string(path) {
+            Ok(v) => v,
+            Err(e) => return Err(Error::Runtime{msg: format!(
+                "couldn't read '{}': {}",
+                rendered_path.display(),
+                e,
+            )}),
+        };
+
+    let ast =
+        match ProgParser::new().parse(Lexer::new(&src)) {
+            Ok(v) => {
+                v
+            },
+            Err(e) => {
+                let ((line, col), msg) = crate::render_parse_error(e);
+
+                return Err(Error::AtLoc{
+                    source: Box::new(Error::ParseModuleFailed{msg}),
+                    line,
+                    col,
+                });
+            },
+        };
+
+    let cur_script_dir = path.parent()
+        .expect("canonical module path should have a parent");
+
+    let module_context = EvaluationContext{
+        builtins: context.builtins,
+        global_bindings: context.global_bindings,
+        env: context.env.clone(),
+        modules: context.modules,
+        cur_script_dir: cur_script_dir.to_path_buf(),
+        cur_script_path: rendered_path.to_path_buf(),
+        cwd: context.cwd,
+        pipefail: &Mutex::new(false),
+    };
+
+    let scopes = super::eval_prog(&module_context, &ast)?;
+
+    // The global bindings aren't exported, because they're declared in a scope
+    // that encloses the top-level scope of the module.
+    let exports = scopes.cur_scope_bindings().into_iter().collect();
+
+    Ok(value::new_object(exports, &Mutability::Const))
+}
@@ -0,0 +1,508 @@
+// Copyright 2026 Sean Kelleher. All rights reserved.
+// Use of this source code is governed by an MIT
+// licence that can be found in the LICENCE file.
+
+use super::error;
+use super::error::Error;
+use super::error::Result;
+use super::scope::Mutability;
+use super::value;
+use super::value::List;
+use super::value::Object;
+use super::value::SourcedValue;
+use super::value::Value;
+use crate::builtins::fns;
+use crate::lock_deref;
+
+const INDENT: &str = "    ";
+
+// `stringify` returns the JSON encoding of `v`. Functions and enums can't be
+// encoded, and strings must be valid UTF-8. If `pretty` is `true` then the
+// items of lists and objects are written on separate, indented lines.
+pub fn stringify(v: &Value, pretty: bool) -> Result<String> {
+    let mut s = String::new();
+    let indent = if pretty { Some(0) } else { None };
+    write_value(&mut s, v, indent)?;
+
+    Ok(s)
+}
+
+// `write_value` writes the JSON encoding of `v` to `s`. `indent` is the
+// nesting depth of `v` if the output is being pretty-printed, and `None`
+// otherwise.
+fn write_value(s: &mut String, v: &Value, indent: Option<usize>)
+    -> Result<()>
+{
+    match v {
+        Value::Null => {
+            s.push_str("null");
+        },
+        Value::Bool(b) => {
+            s.push_str(&b.to_string());
+        },
+        Value::Int(n) => {
+            s.push_str(&n.to_string());
+        },
+        Value::Float(n) => {
+            if !n.is_finite() {
+                return Err(Error::Runtime{msg: format!(
+                    "couldn't encode the float '{}' as JSON",
+                    fns::render_float(*n),
+                )});
+            }
+            s.push_str(&fns::render_float(*n));
+        },
+        Value::Str(raw_str) => {
+            match std::str::from_utf8(raw_str) {
+                Ok(v) => write_str(s, v),
+                Err(e) => return Err(Error::Runtime{msg: format!(
+                    "couldn't encode string as JSON, because it isn't \
+                     valid UTF-8: {e}",
+                )}),
+            }
+        },
+        Value::List{items, ..} => {
+            let items = &lock_deref!(items);
+
+            s.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    s.push(',');
+                }
+                write_newline(s, indent.map(|n| n + 1));
+                write_value(s, &item.v, indent.map(|n| n + 1))?;
+            }
+            if !items.is_empty() {
+                write_newline(s, indent);
+            }
+            s.push(']');
+        },
+        Value::Object{props, ..} => {
+            let props = &lock_deref!(props);
+
+            s.push('{');
+            for (i, (name, prop)) in props.iter().enumerate() {
+                if i > 0 {
+                    s.push(',');
+                }
+                write_newline(s, indent.map(|n| n + 1));
+                write_str(s, name);
+                s.push(':');
+                if indent.is_some() {
+                    s.push(' ');
+                }
+                write_value(s, &prop.v, indent.map(|n| n + 1))?;
+            }
+            if !props.is_empty() {
+                write_newline(s, indent);
+            }
+            s.push('}');
+        },
+        Value::BuiltinFunc{..} |
+        Value::Func(_) |
+        Value::Enum(_) |
+        Value::EnumConstructor{..} |
+        Value::Variant{..} => {
+            return Err(Error::Runtime{msg: format!(
+                "couldn't encode '{}' as JSON",
+                error::render_type(v),
+            )});
+        },
+    }
+
+    Ok(())
+}
+
+fn write_newline(s: &mut String, indent: Option<usize>) {
+    if let Some(n) = indent {
+        s.push('\n');
+        s.push_str(&INDENT.repeat(n));
+    }
+}
+
+fn write_str(s: &mut String, v: &str) {
+    s.push('"');
+    for c in v.chars() {
+        match c {
+            '"' => s.push_str("\\\""),
+            '\\' => s.push_str("\\\\"),
+            '\n' => s.push_str("\\n"),
+            '\r' => s.push_str("\\r"),
+            '\t' => s.push_str("\\t"),
+            c if c.is_control() => s.push_str(&format!("\\u{:04x}", c as u32)),
+            c => s.push(c),
+        }
+    }
+    s.push('"');
+}
+
+// `parse` returns the value that the JSON document `src` represents. Lists and
+// objects in the result are mutable if `mutability` is `Mutability::Var`.
+// Numbers with a fraction or an exponent are parsed as floats, and other
+// numbers are parsed as ints. Ints must fit in 64 bits, floats must be finite,
+// and strings can't contain unpaired surrogates, because such values can't be
+// represented. Lists and objects can't be nested more than `MAX_DEPTH`
+// levels deep.
+pub fn parse(src: &str, mutability: &Mutability) -> Result<SourcedValue> {
+    let mut parser = Parser{
+        chars: src.chars().collect(),
+        index: 0,
+        depth: 0,
+        mutability,
+    };
+
+    parser.skip_whitespace();
+    let v = parser.parse_value()?;
+    parser.skip_whitespace();
+
+    if parser.index < parser.chars.len() {
+        return Err(parser.new_error("unexpected trailing characters"));
+    }
+
+    Ok(v)
+}
+
+const MAX_DEPTH: usize = 512;
+
+struct Parser<'a> {
+    chars: Vec<char>,
+    index: usize,
+    depth: usize,
+    mutability: &'a Mutability,
+}
+
+impl Parser<'_> {
+    fn parse_value(&mut self) -> Result<SourcedValue> {
+        match self.peek() {
+            Some('n') => {
+                self.expect_word("null")?;
+
+                Ok(value::new_null())
+            },
+            Some('t') => {
+                self.expect_word("true")?;
+
+                Ok(value::new_bool(true))
+            },
+            Some('f') => {
+                self.expect_word("false")?;
+
+                Ok(value::new_bool(false))
+            },
+            Some('"') => {
+                let s = self.parse_str()?;
+
+                Ok(value::new_str_from_string(s))
+            },
+            Some('[') => {
+                self.enter_nested()?;
+                let v = self.parse_list()?;
+                self.depth -= 1;
+
+                Ok(v)
+            },
+            Some('{') => {
+                self.enter_nested()?;
+                let v = self.parse_object()?;
+                self.depth -= 1;
+
+                Ok(v)
+            },
+            Some(c) if c == '-' || c.is_ascii_digit() => {
+                self.parse_number()
+            },
+            Some(c) => {
+                Err(self.new_error(&format!("unexpected character '{c}'")))
+            },
+            None => {
+                Err(self.new_error("unexpected end of input"))
+            },
+        }
+    }
+
+    // `enter_nested` records that a list or object is being parsed, and
+    // returns an error if this would exceed `MAX_DEPTH`, so that deeply nested
+    // input can't overflow the stack.
+    fn enter_nested(&mut self) -> Result<()> {
+        if self.depth == MAX_DEPTH {
+            return Err(self.new_error(&format!(
+                "lists and objects can't be nested more than {MAX_DEPTH} \
+                 levels deep",
+            )));
+        }
+        self.depth += 1;
+
+        Ok(())
+    }
+
+    fn parse_list(&mut self) -> Result<SourcedValue> {
+        self.index += 1;
+        self.skip_whitespace();
+
+        let mut items: List = vec![];
+        if self.peek() == Some(']') {
+            self.index += 1;
+
+            return Ok(value::new_list(items, self.mutability));
+        }
+
+        loop {
+            self.skip_whitespace();
+            items.push(self.parse_value()?);
+            self.skip_whitespace();
+
+            match self.peek() {
+                Some(',') => self.index += 1,
+                Some(']') => break,
+                _ => return Err(self.new_error("expected ',' or ']'")),
+            }
+        }
+
+        self.index += 1;
+
+        Ok(value::new_list(items, self.mutability))
+    }
+
+    fn parse_object(&mut self) -> Result<SourcedValue> {
+        self.index += 1;
+        self.skip_whitespace();
+
+        let mut props = Object::new();
+        if self.peek() == Some('}') {
+            self.index += 1;
+
+            return Ok(value::new_object(props, self.mutability));
+        }
+
+        loop {
+            self.skip_whitespace();
+            if self.peek() != Some('"') {
+                return Err(self.new_error("expected a property name"));
+            }
+            let name = self.parse_str()?;
+
+            self.skip_whitespace();
+            if self.peek() != Some(':') {
+                return Err(self.new_error("expected ':'"));
+            }
+            self.index += 1;
+
+            self.skip_whitespace();
+            props.insert(name, self.parse_value()?);
+            self.skip_whitespace();
+
+            match self.peek() {
+                Some(',') => self.index += 1,
+                Some('}') => break,
+                _ => return Err(self.new_error("expected ',' or '}'")),
+            }
+        }
+        self.index += 1;
+
+        Ok(value::new_object(props, self.mutability))
+    }
+
+    fn parse_str(&mut self) -> Result<String> {
+        self.index += 1;
+
+        let mut s = String::new();
+        loop {
+            match self.peek() {
+                Some('"') => {
+                    self.index += 1;
+
+                    return Ok(s);
+                },
+                Some('\\') => {
+                    self.index += 1;
+                    let c = self.parse_escape()?;
+                    s.push(c);
+                },
+                Some(c) if c.is_control() => {
+                    return Err(self.new_error(
+                        "control characters must be escaped in strings",
+                    ));
+                },
+                Some(c) => {
+                    self.index += 1;
+                    s.push(c);
+                },
+                None => {
+                    return Err(self.new_error("unterminated string"));
+                },
+            }
+        }
+    }
+
+    fn parse_escape(&mut self) -> Result<char> {
+        let c =
+            match self.peek() {
+                Some('"') => '"',
+                Some('\\') => '\\',
+                Some('/') => '/',
+                Some('b') => '\u{8}',
+                Some('f') => '\u{c}',
+                Some('n') => '\n',
+                Some('r') => '\r',
+                Some('t') => '\t',
+                Some('u') => {
+                    self.index += 1;
+
+                    return self.parse_unicode_escape();
+                },
+                _ => return Err(self.new_error("invalid escape sequence")),
+            };
+        self.index += 1;
+
+        Ok(c)
+    }
+
+    // `parse_unicode_escape` parses the code unit of a `\u` escape, along with
+    // the low surrogate that follows it if the code unit is a high surrogate.
+    fn parse_unicode_escape(&mut self) -> Result<char> {
+        let hi = self.parse_code_unit()?;
+        if !(0xd800..0xdc00).contains(&hi) {
+            return char::from_u32(hi)
+                .ok_or_else(|| self.new_unpaired_surrogate(hi));
+        }
+
+        if self.chars.get(self.index..self.index+2) != Some(&['\\', 'u']) {
+            return Err(self.new_unpaired_surrogate(hi));
+        }
+        self.index += 2;
+
+        let lo = self.parse_code_unit()?;
+        if !(0xdc00..0xe000).contains(&lo) {
+            return Err(self.new_unpaired_surrogate(hi));
+        }
+
+        let c = 0x10000 + ((hi - 0xd800) << 10) + (lo - 0xdc00);
+
+        char::from_u32(c)
+            .ok_or_else(|| self.new_unpaired_surrogate(hi))
+    }
+
+    fn parse_code_unit(&mut self) -> Result<u32> {
+        let end = self.index + 4;
+        let maybe_digits = self.chars.get(self.index..end);
+        let maybe_n = maybe_digits
+            .map(|digits| digits.iter().collect::<String>())
+            .filter(|s| s.chars().all(|c| c.is_ascii_hexdigit()))
+            .and_then(|s| u32::from_str_radix(&s, 16).ok());
+
+        match maybe_n {
+            Some(n) => {
+                self.index = end;
+
+                Ok(n)
+            },
+            None => {
+                Err(self.new_error("expected 4 hex digits after '\\u'"))
+            },
+        }
+    }
+
+    fn parse_number(&mut self) -> Result<SourcedValue> {
+        let start = self.index;
+        self.consume_while(|c| {
+            c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E')
+        });
+        let num: String = self.chars[start..self.index].iter().collect();
+
+        if !is_valid_number(&num) {
+            self.index = start;
+
+            return Err(self.new_error(&format!("invalid number '{num}'")));
+        }
+
+        if num.contains(['.', 'e', 'E']) {
+            return match num.parse::<f64>() {
+                Ok(n) if n.is_finite() => Ok(value::new_float(n)),
+                _ => Err(Error::Runtime{msg: format!(
+                    "couldn't represent the JSON number '{num}', because it's \
+                     too large for a 'float'",
+                )}),
+            };
+        }
+
+        match num.parse() {
+            Ok(n) => Ok(value::new_int(n)),
+            Err(_) => Err(Error::Runtime{msg: format!(
+                "couldn't represent the JSON number '{num}', because it \
+                 doesn't fit in an 'int'",
+            )}),
+        }
+    }
+
+    fn expect_word(&mut self, word: &str) -> Result<()> {
+        let start = self.index;
+        self.consume_while(|c| c.is_ascii_alphabetic());
+        let got: String = self.chars[start..self.index].iter().collect();
+
+        if got != word {
+            self.index = start;
+
+            return Err(self.new_error(&format!("unexpected word '{got}'")));
+        }
+
+        Ok(())
+    }
+
+    fn skip_whitespace(&mut self) {
+        self.consume_while(|c| matches!(c, ' ' | '\t' | '\n' | '\r'));
+    }
+
+    fn consume_while(&mut self, f: impl Fn(char) -> bool) {
+        while self.peek().is_some_and(&f) {
+            self.index += 1;
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.index).copied()
+    }
+
+    fn new_unpaired_surrogate(&self, code_unit: u32) -> Error {
+        Error::Runtime{msg: format!(
+            "couldn't represent the JSON string, because it contains the \
+             unpaired surrogate '\\u{code_unit:04x}'",
+        )}
+    }
+
+    // `new_error` returns an error for invalid JSON at the line and column of
+    // the current character.
+    fn new_error(&self, msg: &str) -> Error {
+        let before = &self.chars[..self.index.min(self.chars.len())];
+        let line = before.iter().filter(|&&c| c == '\n').count() + 1;
+        let col = before.iter().rev().take_while(|&&c| c != '\n').count() + 1;
+
+        Error::Runtime{msg: format!("invalid JSON at {line}:{col}: {msg}")}
+    }
+}
+
+// `is_valid_number` returns whether `num` matches the JSON number grammar,
+// which is `-?(0|[1-9][0-9]*)(\.[0-9]+)?([eE][+-]?[0-9]+)?`.
+fn is_valid_number(num: &str) -> bool {
+    let num = num.strip_prefix('-').unwrap_or(num);
+
+    let (mantissa, exp) =
+        match num.split_once(['e', 'E']) {
+            Some((mantissa, exp)) => (mantissa, Some(exp)),
+            None => (num, None),
+        };
+    let (int, frac) =
+        match mantissa.split_once('.') {
+            Some((int, frac)) => (int, Some(frac)),
+            None => (mantissa, None),
+        };
+    let exp = exp.map(|e| e.strip_prefix(['+', '-']).unwrap_or(e));
+
+    let is_digits = |s: &str| {
+        !s.is_empty() && s.chars().all(|c| c.is_ascii_digit())
+    };
+
+    is_digits(int) &&
+        (int == "0" || !int.starts_with('0')) &&
+        frac.is_none_or(is_digits) &&
+        exp.is_none_or(is_digits)
+}
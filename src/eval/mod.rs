@@ -7,6 +7,7 @@ use std::collections::HashMap;
 use std::collections::HashSet;
 use std::collections::VecDeque;
 use std::convert::TryInto;
+use std::path::Path;
 use std::path::PathBuf;
 use std::result::Result as StdResult;
 use std::sync::Arc;
@@ -15,6 +16,11 @@ use std::sync::Mutex;
 pub mod bind;
 pub mod builtins;
 pub mod error;
+pub mod format;
+pub mod glob;
+pub mod import;
+pub mod json;
+pub mod proc;
 pub mod scope;
 #[macro_use]
 pub mod value;
@@ -31,17 +37,18 @@ use self::builtins::Builtins;
 #[allow(clippy::wildcard_imports)]
 use self::error::*;
 use self::error::Error;
+use self::import::Modules;
 use self::scope::Mutability;
 use self::scope::ScopeStack;
 use self::value::BuiltinFunc;
+use self::value::EnumType;
 use self::value::Func;
 use self::value::ListRef;
+use self::value::ObjectRef;
 use self::value::SourcedValue;
 use self::value::Str;
 use self::value::Value;
 
-use crate::lexer::Lexer;
-use crate::parser::ExprParser;
 
 macro_rules! match_eval_expr {
     (
@@ -56,32 +63,105 @@ macro_rules! match_eval_expr {
     }};
 }
 
+#[derive(Clone)]
 pub struct EvaluationContext<'a> {
     pub builtins: &'a Builtins,
-    // TODO `cur_script_dir` will later be exposed by reflection imports.
-    #[allow(dead_code)]
+    // `global_bindings` are declared in a scope that encloses the top-level
+    // scope of every script and module that is evaluated.
+    pub global_bindings: &'a [(RawExpr, SourcedValue)],
+    // `env` holds the environment variables that are passed to external
+    // commands.
+    pub env: ObjectRef,
+    pub modules: &'a Mutex<Modules>,
+    // `cur_script_dir` is the directory of the script or module that is
+    // currently being evaluated, which imports are resolved relative to.
     pub cur_script_dir: PathBuf,
+    // `cur_script_path` is the path of the script or module that is currently
+    // being evaluated, as it's rendered in error messages.
+    pub cur_script_path: PathBuf,
+    // `cwd` is the working directory of the script, which relative paths are
+    // resolved against, and which commands are run in. It's tracked here
+    // instead of being set on the process so that it's shared by every module
+    // that's evaluated, and can't be changed from outside the interpreter.
+    pub cwd: &'a Mutex<PathBuf>,
+    // `pipefail` defines whether a pipeline is considered to fail if any of
+    // its stages fail, instead of only when its last stage fails, like Bash's
+    // `pipefail` option. Each script and module has its own setting, so that
+    // a module can't change how the pipelines of its importer are evaluated.
+    pub pipefail: &'a Mutex<bool>,
 }
 
-pub fn eval_prog(
-    context: &EvaluationContext,
-    scopes: &mut ScopeStack,
-    global_bindings: Vec<(RawExpr, SourcedValue)>,
-    Prog::Body{stmts}: &Prog,
-)
-    -> Result<()>
+impl EvaluationContext<'_> {
+    pub fn cwd(&self) -> PathBuf {
+        let cwd = self.cwd;
+
+        lock_deref!(cwd).clone()
+    }
+
+    pub fn set_cwd(&self, dir: PathBuf) {
+        let cwd = self.cwd;
+
+        lock_deref!(cwd) = dir;
+    }
+
+    pub fn pipefail(&self) -> bool {
+        let pipefail = self.pipefail;
+
+        lock_deref!(pipefail)
+    }
+
+    pub fn set_pipefail(&self, enabled: bool) {
+        let pipefail = self.pipefail;
+
+        lock_deref!(pipefail) = enabled;
+    }
+
+    // `resolve_path` returns `path` resolved relative to the working directory
+    // of the script.
+    pub fn resolve_path(&self, path: &Path) -> PathBuf {
+        self.cwd().join(path)
+    }
+}
+
+// `eval_prog` evaluates `stmts` in a new scope that contains the global
+// bindings of `context`, and returns the scope stack that `stmts` were
+// evaluated in, so that the top-level declarations can be accessed.
+pub fn eval_prog(context: &EvaluationContext, Prog::Body{stmts}: &Prog)
+    -> Result<ScopeStack>
 {
-    let bindings =
-        global_bindings
-            .into_iter()
-            .map(|(raw_expr, v)| ((raw_expr, (0, 0)), v))
-            .collect();
+    let mut global_scopes =
+        ScopeStack::new(vec![]).new_from_push(HashMap::new());
+
+    // The standard library is bound alongside the other global bindings, so
+    // that it's available to every script and module.
+    let std_binding = (
+        RawExpr::Var{name: "std".to_string()},
+        value::new_val_ref_with_no_source(Value::Object{
+            props: context.builtins.std.clone(),
+            is_mutable: false,
+        }),
+    );
+
+    for (raw_expr, v) in context.global_bindings.iter().chain([&std_binding]) {
+        bind::bind(
+            context,
+            &mut global_scopes,
+            &(raw_expr.clone(), (0, 0)),
+            v.clone(),
+            BindType::VarDeclaration
+        )
+            .context(BindFailed)?;
+    }
 
-    let v = eval_stmts(context, scopes, bindings, stmts)
-        .context(EvalStmtsFailed)?;
+    // We evaluate the program in a new scope so that it can shadow the global
+    // bindings.
+    let mut scopes = global_scopes.new_from_push(HashMap::new());
+
+    let v = eval_stmts_with_scope_stack(context, &mut scopes, stmts)
+        .context(EvalStmtsWithScopeStackFailed)?;
 
     match v {
-        Escape::None => Ok(()),
+        Escape::None => Ok(scopes),
         Escape::Break{loc} => {
             let (line, col) = loc;
 
@@ -246,6 +326,64 @@ fn eval_stmt(
             }
         },
 
+        Stmt::Match{loc, expr, arms} => {
+            let v = eval_expr(context, scopes, expr)
+                .context(EvalMatchValueFailed)?;
+
+            for MatchArm{pattern, guard, stmts} in arms {
+                let fits = bind::fits(context, scopes, pattern, &v.v)
+                    .context(CheckMatchPatternFailed)?;
+
+                if !fits {
+                    continue;
+                }
+
+                let mut new_scopes = scopes.new_from_push(HashMap::new());
+
+                bind::bind(
+                    context,
+                    &mut new_scopes,
+                    pattern,
+                    v.clone(),
+                    BindType::Match,
+                )
+                    .context(BindMatchPatternFailed)?;
+
+                if let Some(guard) = guard {
+                    let b =
+                        eval_expr_to_bool(
+                            context,
+                            &mut new_scopes,
+                            "guard",
+                            guard,
+                        )
+                            .context(EvalMatchGuardFailed)?;
+
+                    if !b {
+                        continue;
+                    }
+                }
+
+                let escape =
+                    eval_stmts_with_scope_stack(
+                        context,
+                        &mut new_scopes,
+                        stmts,
+                    )
+                        .context(EvalMatchStatementsFailed)?;
+
+                return Ok(escape);
+            }
+
+            let (line, col) = loc;
+
+            return Err(Error::AtLoc{
+                source: Box::new(Error::NoMatchingArm{value: v.v}),
+                line: *line,
+                col: *col,
+            });
+        },
+
         Stmt::While{cond, stmts} => {
             loop {
                 let b = eval_expr_to_bool(context, scopes, "condition", cond)
@@ -306,6 +444,7 @@ fn eval_stmt(
 
             let closure = scopes.clone();
             let func = value::new_func(
+                context,
                 Some(name.clone()),
                 args.clone(),
                 *collect_args,
@@ -326,11 +465,59 @@ fn eval_stmt(
 
             return Ok(Escape::Return{value: v, loc: *loc});
         },
+
+        Stmt::Enum{name: (name, loc), variants} => {
+            validate_variants(variants)
+                .context(ValidateVariantsFailed)?;
+
+            let typ = EnumType{name: name.clone(), variants: variants.clone()};
+            let v = value::new_val_ref_with_no_source(
+                Value::Enum(Arc::new(typ)),
+            );
+
+            bind::bind_name(scopes, name, loc, v, BindType::ConstDeclaration)
+                .context(DeclareEnumFailed)?;
+        },
     }
 
     Ok(Escape::None)
 }
 
+// `validate_variants` checks that the names of `variants` are unique, and that
+// the field names of each variant are unique.
+fn validate_variants(variants: &[EnumVariant]) -> Result<()> {
+    let mut name_locs = HashMap::<&String, &Location>::new();
+
+    for EnumVariant{name: (name, loc), fields} in variants {
+        let new_loc_err = |source| {
+            let (line, col) = loc;
+
+            Err(Error::AtLoc{source: Box::new(source), line: *line, col: *col})
+        };
+
+        if let Some((line, col)) = name_locs.get(name) {
+            return new_loc_err(Error::DupVariantName{
+                name: name.to_string(),
+                line: *line,
+                col: *col,
+            });
+        }
+        name_locs.insert(name, loc);
+
+        let mut field_names = HashSet::new();
+        for field in fields {
+            if !field_names.insert(field) {
+                return new_loc_err(Error::DupVariantField{
+                    variant: name.to_string(),
+                    field: field.to_string(),
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
+
 fn validate_args(args: &[Expr]) -> Result<()> {
     let mut queue = VecDeque::from(args.to_owned());
     let mut name_locs = HashMap::<String, Location>::new();
@@ -402,6 +589,8 @@ fn validate_args(args: &[Expr]) -> Result<()> {
                 return new_invalid_bind_error("a boolean literal"),
             RawExpr::Int{..} =>
                 return new_invalid_bind_error("an integer literal"),
+            RawExpr::Float{..} =>
+                return new_invalid_bind_error("a float literal"),
             RawExpr::Str{..} =>
                 return new_invalid_bind_error("a string literal"),
             RawExpr::UnaryOp{..} =>
@@ -412,10 +601,25 @@ fn validate_args(args: &[Expr]) -> Result<()> {
                 return new_invalid_bind_error("a range operation"),
             RawExpr::Func{..} =>
                 return new_invalid_bind_error("an anonymous function"),
-            RawExpr::Call{..} =>
-                return new_invalid_bind_error("a function call"),
+            // A call is only valid as a parameter if it's a variant
+            // destructure, which is checked when the parameter is bound.
+            RawExpr::Call{args: call_args, ..} => {
+                for ListItem{expr, is_spread} in call_args {
+                    if is_spread {
+                        return new_loc_err(Error::ItemSpreadInParamList);
+                    }
+
+                    queue.push_back(expr.clone());
+                }
+            },
             RawExpr::CatchAsBool{..} =>
                 return new_invalid_bind_error("a boolean catch"),
+            RawExpr::Command{..} =>
+                return new_invalid_bind_error("a command"),
+            RawExpr::Pipeline{..} =>
+                return new_invalid_bind_error("a pipeline"),
+            RawExpr::Import{..} =>
+                return new_invalid_bind_error("an import"),
         }
     }
 
@@ -424,7 +628,7 @@ fn validate_args(args: &[Expr]) -> Result<()> {
 
 // `value_to_pairs` returns the "index, value" pairs in `v`, if `v` represents
 // an "iterable" type.
-fn value_to_pairs(v: &Value) -> Result<Vec<(SourcedValue, SourcedValue)>> {
+pub fn value_to_pairs(v: &Value) -> Result<Vec<(SourcedValue, SourcedValue)>> {
     match v {
         Value::Str(s) => {
             let mut pairs = Vec::with_capacity(s.len());
@@ -502,18 +706,13 @@ fn eval_expr(
         RawExpr::Bool{b} => Ok(value::new_bool(*b)),
 
         RawExpr::Int{n} => Ok(value::new_int(*n)),
+        RawExpr::Float{n} => Ok(value::new_float(*n)),
 
         RawExpr::Str{s, interpolation_slots} => {
             if let Some(slots) = interpolation_slots {
-                let v =
-                    interpolate_string(
-                        context,
-                        scopes,
-                        s,
-                        slots,
-                        (line, col),
-                    )
+                let v = interpolate_string(context, scopes, s, slots)
                     .context(InterpolateStringFailed)?;
+
                 Ok(value::new_str_from_string(v))
             } else {
                 Ok(value::new_str_from_string(s.clone()))
@@ -846,14 +1045,20 @@ fn eval_expr(
                             &context.builtins.type_functions.bools,
                         Value::Int(_) =>
                             &context.builtins.type_functions.ints,
+                        Value::Float(_) =>
+                            &context.builtins.type_functions.floats,
                         Value::Str(_) =>
                             &context.builtins.type_functions.strs,
                         Value::List{..} =>
                             &context.builtins.type_functions.lists,
                         Value::Object{..} =>
                             &context.builtins.type_functions.objects,
-                        Value::BuiltinFunc{..} | Value::Func{..}  =>
+                        Value::BuiltinFunc{..} |
+                        Value::Func{..} |
+                        Value::EnumConstructor{..} =>
                             &context.builtins.type_functions.funcs,
+                        Value::Enum(_) | Value::Variant{..} =>
+                            &context.builtins.type_functions.enums,
 
                         Value::Null => {
                             return new_loc_err(Error::TypeFunctionOnNull)
@@ -863,6 +1068,15 @@ fn eval_expr(
                     match source.v {
                         Value::Object{ref props, ..} => props,
 
+                        Value::Enum(ref typ) => {
+                            return match variant_prop(typ, name) {
+                                Some(v) => Ok(v),
+                                None => new_loc_err(
+                                    new_no_such_variant(typ, name),
+                                ),
+                            };
+                        },
+
                         value => {
                             return new_loc_err(Error::PropAccessOnNonObject{
                                 value,
@@ -895,6 +1109,7 @@ fn eval_expr(
             let closure = scopes.clone();
 
             Ok(value::new_func(
+                context,
                 None,
                 args.clone(),
                 *collect_args,
@@ -933,6 +1148,107 @@ fn eval_expr(
                 &Mutability::Const,
             ))
         },
+
+        RawExpr::Command{args} => {
+            let cmd = eval_command(context, scopes, args, (line, col))
+                .context(EvalCommandFailed)?;
+
+            let output =
+                match proc::run(&cmd, proc::Redirects::default()) {
+                    Ok(v) => v,
+                    Err(e) => return new_loc_err(Error::Runtime{
+                        msg: proc::render_run_error(&cmd.argv, &e),
+                    }),
+                };
+
+            let maybe_failure =
+                proc::render_failure(
+                    &proc::render_argv(&cmd.argv),
+                    output.exit_code,
+                    &output.stderr,
+                );
+            if let Some(msg) = maybe_failure {
+                return new_loc_err(Error::Runtime{msg});
+            }
+
+            Ok(proc::new_output_object(output))
+        },
+
+        RawExpr::Pipeline{stages} => {
+            let mut cmds = vec![];
+            for (raw_stage, (stage_line, stage_col)) in stages {
+                let new_stage_err = |source| {
+                    Err(Error::AtLoc{
+                        source: Box::new(source),
+                        line: *stage_line,
+                        col: *stage_col,
+                    })
+                };
+
+                let args =
+                    if let RawExpr::Command{args} = raw_stage {
+                        args
+                    } else {
+                        return new_stage_err(Error::PipelineStageNotCommand);
+                    };
+
+                let cmd =
+                    eval_command(
+                        context,
+                        scopes,
+                        args,
+                        (stage_line, stage_col),
+                    )
+                        .context(EvalPipelineStageFailed)?;
+
+                cmds.push(cmd);
+            }
+
+            let output =
+                match proc::run_pipeline(&cmds) {
+                    Ok(v) => v,
+                    Err((i, e)) => {
+                        let (_, (stage_line, stage_col)) = &stages[i];
+
+                        return Err(Error::AtLoc{
+                            source: Box::new(Error::Runtime{msg: format!(
+                                "couldn't run `{}`: {}",
+                                proc::render_argv(&cmds[i].argv),
+                                e,
+                            )}),
+                            line: *stage_line,
+                            col: *stage_col,
+                        });
+                    },
+                };
+
+            let pipefail = context.pipefail();
+            let maybe_failure =
+                proc::render_failure(
+                    &proc::render_pipeline(&cmds),
+                    output.exit_code(pipefail),
+                    &output.stderr,
+                );
+            if let Some(msg) = maybe_failure {
+                return new_loc_err(Error::Runtime{msg});
+            }
+
+            Ok(proc::new_pipeline_output_object(&output, pipefail))
+        },
+
+        RawExpr::Import{path} => {
+            import::import(context, path, (*line, *col))
+        },
+    }
+}
+
+// `exit_code` returns the exit code passed to `exit`, if `err` was caused by a
+// call to `exit`.
+pub fn exit_code(err: &Error) -> Option<i32> {
+    if let Error::Exit{code} = root_error(err) {
+        Some(*code)
+    } else {
+        None
     }
 }
 
@@ -967,6 +1283,8 @@ enum CallBinding {
         bindings: Vec<(Expr, SourcedValue)>,
         closure: ScopeStack,
         stmts: Block,
+        script_dir: PathBuf,
+        script_path: PathBuf,
     },
 }
 
@@ -1001,6 +1319,16 @@ fn apply_unary_operation(
                 },
             }
         },
+        UnaryOp::BitNot => {
+            match value {
+                Value::Int(n) => {
+                    Ok(Value::Int(!n))
+                },
+                _ => {
+                    Err(new_invalid_op_type())
+                },
+            }
+        },
     }
 }
 
@@ -1100,6 +1428,20 @@ fn apply_binary_operation(
             col: *col,
         }
     };
+    let new_invalid_rhs = |lhs: &i64, rhs: &i64, descr: &str, reqr: &str| {
+        Error::AtLoc{
+            source: Box::new(Error::Runtime{msg: format!(
+                "the {} in '{} {} {}' must be {}",
+                descr,
+                lhs,
+                error::bin_op_symbol(op),
+                rhs,
+                reqr,
+            )}),
+            line: *line,
+            col: *col,
+        }
+    };
 
     match op {
         BinaryOp::Eq |
@@ -1153,6 +1495,9 @@ fn apply_binary_operation(
                         Err(new_int_overflow(a, b))
                     }
                 },
+                (Value::Float(a), Value::Float(b)) => {
+                    Ok(Value::Float(a + b))
+                },
                 (Value::Str(a), Value::Str(b)) => {
                     Ok(Value::Str([a.clone(), b.clone()].concat()))
                 },
@@ -1211,6 +1556,109 @@ fn apply_binary_operation(
                     }
                 },
 
+                (Value::Float(a), Value::Float(b)) => {
+                    let v =
+                        match op {
+                            BinaryOp::Sub => a - b,
+                            BinaryOp::Mul => a * b,
+                            BinaryOp::Div => a / b,
+                            BinaryOp::Mod => a % b,
+
+                            _ => panic!("unexpected operation"),
+                        };
+
+                    Ok(Value::Float(v))
+                },
+
+                _ => {
+                    Err(new_invalid_op_types())
+                },
+            }
+        },
+
+        BinaryOp::Pow => {
+            match (lhs, rhs) {
+                (Value::Int(a), Value::Int(b)) => {
+                    let exp =
+                        match u32::try_from(*b) {
+                            Ok(exp) => exp,
+                            Err(_) if *b < 0 => {
+                                return Err(new_invalid_rhs(
+                                    a,
+                                    b,
+                                    "exponent",
+                                    "non-negative",
+                                ));
+                            },
+                            Err(_) => {
+                                return Err(new_int_overflow(a, b));
+                            },
+                        };
+
+                    if let Some(v) = a.checked_pow(exp) {
+                        Ok(Value::Int(v))
+                    } else {
+                        Err(new_int_overflow(a, b))
+                    }
+                },
+
+                _ => {
+                    Err(new_invalid_op_types())
+                },
+            }
+        },
+
+        BinaryOp::BitAnd |
+        BinaryOp::BitOr |
+        BinaryOp::BitXor => {
+            match (lhs, rhs) {
+                (Value::Int(a), Value::Int(b)) => {
+                    let v =
+                        match op {
+                            BinaryOp::BitAnd => a & b,
+                            BinaryOp::BitOr => a | b,
+                            BinaryOp::BitXor => a ^ b,
+
+                            _ => panic!("unexpected operation"),
+                        };
+
+                    Ok(Value::Int(v))
+                },
+
+                _ => {
+                    Err(new_invalid_op_types())
+                },
+            }
+        },
+
+        // Shifts discard the bits that are shifted out of the int, and `>>`
+        // preserves the sign of the int, as is the case in most
+        // implementations of C.
+        BinaryOp::Shl |
+        BinaryOp::Shr => {
+            match (lhs, rhs) {
+                (Value::Int(a), Value::Int(b)) => {
+                    let maybe_v =
+                        u32::try_from(*b)
+                            .ok()
+                            .and_then(|n| {
+                                match op {
+                                    BinaryOp::Shl => a.checked_shl(n),
+                                    _ => a.checked_shr(n),
+                                }
+                            });
+
+                    match maybe_v {
+                        Some(v) => Ok(Value::Int(v)),
+                        None => Err(new_invalid_rhs(
+                            a,
+                            b,
+                            "shift amount",
+                            "between 0 and 63",
+                        )),
+                    }
+                },
+
                 _ => {
                     Err(new_invalid_op_types())
                 },
@@ -1241,6 +1689,20 @@ fn apply_binary_operation(
                     Ok(Value::Bool(v))
                 },
 
+                (Value::Float(a), Value::Float(b)) => {
+                    let v =
+                        match op {
+                            BinaryOp::Gt => a > b,
+                            BinaryOp::Gte => a >= b,
+                            BinaryOp::Lt => a < b,
+                            BinaryOp::Lte => a <= b,
+
+                            _ => panic!("unexpected operation"),
+                        };
+
+                    Ok(Value::Bool(v))
+                },
+
                 _ => {
                     Err(new_invalid_op_types())
                 },
@@ -1251,7 +1713,9 @@ fn apply_binary_operation(
 
 // `eq` returns a path to the values in `lhs` and `rhs` that differ, and the
 // type that differ, if `lhs` and `rhs` are of different types.
-fn eq(lhs: &Value, rhs: &Value) -> StdResult<bool, (String, String, String)> {
+pub fn eq(lhs: &Value, rhs: &Value)
+    -> StdResult<bool, (String, String, String)>
+{
     match (lhs, rhs) {
         (Value::Null, Value::Null) =>
             Ok(true),
@@ -1262,6 +1726,9 @@ fn eq(lhs: &Value, rhs: &Value) -> StdResult<bool, (String, String, String)> {
         (Value::Int(a), Value::Int(b)) =>
             Ok(a == b),
 
+        (Value::Float(a), Value::Float(b)) =>
+            Ok(a == b),
+
         (Value::Str(a), Value::Str(b)) =>
             Ok(a == b),
 
@@ -1331,6 +1798,36 @@ fn eq(lhs: &Value, rhs: &Value) -> StdResult<bool, (String, String, String)> {
             Ok(true)
         },
 
+        (Value::Enum(a), Value::Enum(b)) if Arc::ptr_eq(a, b) =>
+            Ok(true),
+
+        (
+            Value::Variant{typ: a_typ, variant: a_variant, payload: xs},
+            Value::Variant{typ: b_typ, variant: b_variant, payload: ys},
+        ) if Arc::ptr_eq(a_typ, b_typ) => {
+            if a_variant != b_variant {
+                return Ok(false);
+            }
+
+            for (i, (x, y)) in xs.iter().zip(ys).enumerate() {
+                let equal =
+                    match eq(&x.v, &y.v) {
+                        Ok(v) => v,
+                        Err((path, a, b)) => return Err((
+                            format!("({i}){path}"),
+                            a,
+                            b,
+                        )),
+                    };
+
+                if !equal {
+                    return Ok(false);
+                }
+            }
+
+            Ok(true)
+        },
+
         _ =>
             Err((
                 String::new(),
@@ -1340,6 +1837,27 @@ fn eq(lhs: &Value, rhs: &Value) -> StdResult<bool, (String, String, String)> {
     }
 }
 
+// `variant_prop` returns the variant of `typ` named `name`, which is a
+// constructor if the variant has a payload.
+fn variant_prop(typ: &Arc<EnumType>, name: &str) -> Option<SourcedValue> {
+    let variant = value::variant_index(typ, name)?;
+
+    if typ.variants[variant].fields.is_empty() {
+        return Some(value::new_variant(typ, variant, vec![]));
+    }
+
+    Some(value::new_val_ref_with_no_source(Value::EnumConstructor{
+        typ: typ.clone(),
+        variant,
+    }))
+}
+
+pub fn new_no_such_variant(typ: &EnumType, name: &str) -> Error {
+    Error::Runtime{
+        msg: format!("enum '{}' doesn't have a variant '{name}'", typ.name),
+    }
+}
+
 fn ref_eq(lhs: &Value, rhs: &Value) -> Option<bool> {
     match (lhs, rhs) {
         (Value::List{items: a, ..}, Value::List{items: b, ..}) => {
@@ -1354,6 +1872,10 @@ fn ref_eq(lhs: &Value, rhs: &Value) -> Option<bool> {
             Some(value::ref_eq(a, b))
         },
 
+        (Value::Enum(a), Value::Enum(b)) => {
+            Some(Arc::ptr_eq(a, b))
+        },
+
         _ =>
             None,
     }
@@ -1544,27 +2066,99 @@ fn eval_list_items(
     Ok(vals)
 }
 
-#[allow(clippy::too_many_lines)]
-fn eval_call(
+// `eval_command` evaluates `args` to a command. Every argument must evaluate
+// to a string, so that arguments are passed to the command exactly as they
+// were given. The first argument may instead evaluate to an object, in which
+// case its properties are used as environment variables for the command, on
+// top of those defined by `env`.
+fn eval_command(
     context: &EvaluationContext,
     scopes: &mut ScopeStack,
-    func: &Expr,
     args: &Vec<ListItem>,
     loc: (&usize, &usize),
 )
-    -> Result<SourcedValue>
+    -> Result<proc::Cmd>
 {
     let (line, col) = loc;
     let new_loc_err = |source| {
         Err(Error::AtLoc{source: Box::new(source), line: *line, col: *col})
     };
 
+    let mut vals = eval_list_items(context, scopes, args)
+        .context(EvalListItemsFailed)?;
+
+    let mut overrides = None;
+    if let Some(Value::Object{props, ..}) = vals.first().map(|v| &v.v) {
+        overrides = Some(props.clone());
+        vals.remove(0);
+    }
+
+    let mut argv = vec![];
+    for (i, v) in vals.into_iter().enumerate() {
+        match v.v {
+            Value::Str(s) => {
+                argv.push(s);
+            },
+            value => {
+                return new_loc_err(Error::IncorrectType{
+                    descr: format!("command argument {i}"),
+                    exp_type: "string".to_string(),
+                    value,
+                });
+            },
+        }
+    }
+
+    if argv.is_empty() {
+        return new_loc_err(Error::EmptyCommand);
+    }
+
+    let env =
+        match proc::new_env(&context.env, overrides.as_ref()) {
+            Ok(v) => v,
+            Err(source) => return new_loc_err(source),
+        };
+
+    Ok(proc::Cmd{argv, env, dir: context.cwd()})
+}
+
+fn eval_call(
+    context: &EvaluationContext,
+    scopes: &mut ScopeStack,
+    func: &Expr,
+    args: &Vec<ListItem>,
+    loc: (&usize, &usize),
+)
+    -> Result<SourcedValue>
+{
     let arg_vals = eval_list_items(context, scopes, args)
         .context(EvalCallArgsFailed)?;
 
     let func_val = eval_expr(context, scopes, func)
         .context(EvalCallFuncFailed)?;
 
+    let (line, col) = loc;
+
+    call_func(context, func_val, arg_vals, (*line, *col))
+}
+
+// `call_func` calls `func_val` with `arg_vals`, where `loc` is the location of
+// the call. Built-in functions can use `call_func` to call functions that
+// they're passed.
+#[allow(clippy::too_many_lines)]
+pub fn call_func(
+    context: &EvaluationContext,
+    func_val: SourcedValue,
+    arg_vals: Vec<SourcedValue>,
+    loc: (usize, usize),
+)
+    -> Result<SourcedValue>
+{
+    let (line, col) = loc;
+    let new_loc_err = |source| {
+        Err(Error::AtLoc{source: Box::new(source), line, col})
+    };
+
     let (func_name, v) =
         {
             let SourcedValue{v, source} = func_val;
@@ -1586,6 +2180,8 @@ fn eval_call(
                         collect_args,
                         stmts,
                         closure,
+                        script_dir,
+                        script_path,
                     } = &lock_deref!(f);
 
                     let num_params = arg_names.len();
@@ -1641,10 +2237,22 @@ fn eval_call(
                             bindings,
                             closure: closure.clone(),
                             stmts: stmts.clone(),
+                            script_dir: script_dir.clone(),
+                            script_path: script_path.clone(),
                         },
                     )
                 },
 
+                Value::EnumConstructor{typ, variant} => {
+                    let need = typ.variants[variant].fields.len();
+                    let got = arg_vals.len();
+                    if need != got {
+                        return new_loc_err(Error::ArgNumMismatch{need, got});
+                    }
+
+                    return Ok(value::new_variant(&typ, variant, arg_vals));
+                },
+
                 _ => {
                     return new_loc_err(Error::CannotCallNonFunc{v});
                 },
@@ -1654,23 +2262,39 @@ fn eval_call(
     let v =
         match v {
             CallBinding::BuiltinFunc{f, this, args} => {
-                f(this, args)
+                f(context, (line, col), this, args)
                     .context(EvalBuiltinFuncCallFailed{
                         func_name,
-                        call_loc: (*line, *col),
+                        call_loc: (line, col),
                     })?
             },
 
-            CallBinding::Func{bindings, mut closure, stmts} => {
+            CallBinding::Func{
+                bindings,
+                mut closure,
+                stmts,
+                script_dir,
+                script_path,
+            } => {
+                // The function is evaluated in the context of the script that
+                // it was defined in, so that its imports are resolved relative
+                // to that script, and so that its errors refer to that script.
+                let func_context = EvaluationContext{
+                    cur_script_dir: script_dir,
+                    cur_script_path: script_path.clone(),
+                    ..context.clone()
+                };
+
                 let v = eval_stmts(
-                    context,
+                    &func_context,
                     &mut closure,
                     bindings,
                     &stmts,
                 )
                     .context(EvalFuncCallFailed{
                         func_name,
-                        call_loc: (*line, *col),
+                        func_path: script_path,
+                        call_loc: (line, col),
                     })?;
 
                 match v {
@@ -1693,80 +2317,33 @@ fn interpolate_string(
     context: &EvaluationContext,
     scopes: &mut ScopeStack,
     s: &str,
-    interpolation_slots: &Vec<(usize, usize)>,
-    loc: (&usize, &usize),
+    interpolation_slots: &Vec<InterpolationSlot>,
 )
     -> Result<String>
 {
-    let (line, col) = loc;
-    let new_loc_err = |source, col| {
-        Err(Error::AtLoc{source: Box::new(source), line: *line, col})
-    };
-
-    let parser = ExprParser::new();
-
     let mut result: Vec<String> = vec![];
 
     let mut last_slot_end = 0;
 
-    for cur_slot in interpolation_slots {
-        let (cur_slot_start, cur_slot_end) = cur_slot;
-        result.push(s[last_slot_end .. *cur_slot_start].to_string());
-
-        // We shorten the slot to skip the delimiters (`${` at the start and
-        // `}` at the end).
-        let directive = &s[(cur_slot_start+2) .. (cur_slot_end-1)];
+    for slot in interpolation_slots {
+        let InterpolationSlot{start, end, expr, format_spec} = slot;
+        let (_, (line, col)) = expr;
 
-        let slot_col = col + cur_slot_start + 4;
+        result.push(s[last_slot_end .. *start].to_string());
 
-        let mut lexer = Lexer::new(directive);
+        let v = eval_expr(context, scopes, expr)
+            .context(InterpolateStringEvalExprFailed)?;
 
-        let ast =
-            match parser.parse(&mut lexer) {
-                Ok(v) => v,
-                Err(e) => return new_loc_err(
-                    Error::InterpolateStringParseFailed{
-                        source_str: format!("{e:?}"),
-                    },
-                    slot_col,
-                ),
-            };
-
-        // We catch the evaluation error manually so that we can modify the
-        // location of the error to account for the string location.
-        let v =
-            match eval_expr(context, scopes, &ast) {
-                Ok(v) => v,
-                Err(e) => return new_loc_err(
-                    Error::InterpolateStringEvalExprFailed{
-                        source: Box::new(e),
-                    },
-                    slot_col,
-                ),
-            };
-
-        match v.v {
-            Value::Str(s) => {
-                match String::from_utf8(s.clone()) {
-                    Ok(s) => result.push(s),
-                    Err(source) => return new_loc_err(
-                        Error::StringConstructionFailed{
-                            source,
-                            descr: "interpolated slot".to_string(),
-                        },
-                        slot_col,
-                    ),
-                }
-            },
-            value => {
-                return new_loc_err(
-                    Error::InterpolatedValueNotString{value},
-                    slot_col,
-                );
-            },
+        match format::format(&v, format_spec.as_ref()) {
+            Ok(s) => result.push(s),
+            Err(source) => return Err(Error::AtLoc{
+                source: Box::new(source),
+                line: *line,
+                col: *col,
+            }),
         }
 
-        last_slot_end = *cur_slot_end;
+        last_slot_end = *end;
     }
 
     result.push(s[last_slot_end ..].to_string());
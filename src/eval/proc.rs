@@ -0,0 +1,494 @@
+// Copyright 2026 Sean Kelleher. All rights reserved.
+// Use of this source code is governed by an MIT
+// licence that can be found in the LICENCE file.
+
+use std::collections::BTreeMap;
+use std::ffi::OsString;
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::io;
+use std::io::Error as IoError;
+use std::io::Read;
+use std::io::Write;
+use std::os::unix::ffi::OsStringExt;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Child;
+use std::process::Command;
+use std::process::Stdio;
+use std::thread;
+
+use super::error::Error;
+use super::scope::Mutability;
+use super::value;
+use super::value::ObjectRef;
+use super::value::SourcedValue;
+use super::value::Str;
+use super::value::Value;
+use crate::lock_deref;
+
+// `Output` is the result of running an external command to completion.
+pub struct Output {
+    pub stdout: Str,
+    pub stderr: Str,
+    // `exit_code` is `None` if the process was terminated by a signal.
+    pub exit_code: Option<i32>,
+}
+
+// `Cmd` is an external command to run.
+pub struct Cmd {
+    pub argv: Vec<Str>,
+    // `env` is the complete environment of the process, rather than a set of
+    // additions to the environment of the current process.
+    pub env: Env,
+    // `dir` is the working directory of the process, which relative redirect
+    // paths are also resolved against.
+    pub dir: PathBuf,
+}
+
+pub type Env = BTreeMap<String, Str>;
+
+// `new_env` creates the environment of a process from the properties of
+// `env`, with the properties of `overrides` applied on top. Variables that are
+// set to `null` are removed from the environment.
+pub fn new_env(env: &ObjectRef, overrides: Option<&ObjectRef>)
+    -> Result<Env, Error>
+{
+    let mut vars = Env::new();
+
+    apply_env(&mut vars, env)?;
+    if let Some(o) = overrides {
+        apply_env(&mut vars, o)?;
+    }
+
+    Ok(vars)
+}
+
+fn apply_env(vars: &mut Env, env: &ObjectRef) -> Result<(), Error> {
+    for (name, v) in &lock_deref!(env) {
+        match &v.v {
+            Value::Str(s) => {
+                vars.insert(name.clone(), s.clone());
+            },
+            Value::Null => {
+                vars.remove(name);
+            },
+            value => {
+                return Err(Error::IncorrectType{
+                    descr: format!("environment variable '{name}'"),
+                    exp_type: "string".to_string(),
+                    value: value.clone(),
+                });
+            },
+        }
+    }
+
+    Ok(())
+}
+
+// `Redirects` defines where the standard streams of a process are read from
+// and written to. By default, `stdin` is inherited from the current process,
+// and `stdout` and `stderr` are captured.
+#[derive(Default)]
+pub struct Redirects {
+    pub stdin: Stdin,
+    pub stdout_file: Option<PathBuf>,
+    pub stderr_file: Option<PathBuf>,
+    // `append` defines whether `stdout_file` and `stderr_file` are appended to
+    // instead of being truncated.
+    pub append: bool,
+    pub stderr_to_stdout: bool,
+}
+
+#[derive(Default)]
+pub enum Stdin {
+    #[default]
+    Inherit,
+    File(PathBuf),
+    // `Bytes` is written directly to the `stdin` of the process, through a
+    // pipe.
+    Bytes(Str),
+}
+
+#[derive(Debug)]
+pub enum RunError {
+    RedirectFailed{path: PathBuf, source: IoError},
+    // `PipeSetup` is returned if a pipe couldn't be created, or a handle
+    // couldn't be duplicated, while connecting the streams of the process.
+    PipeSetup{source: IoError},
+    SpawnFailed{source: IoError},
+}
+
+// `run` runs the program named by the first item of `argv`, passing the
+// remaining items as its arguments. Arguments are passed to the program as-is,
+// so no word-splitting or globbing is performed on them.
+pub fn run(cmd: &Cmd, redirects: Redirects) -> Result<Output, RunError> {
+    let Redirects{
+        stdin,
+        stdout_file,
+        stderr_file,
+        append,
+        stderr_to_stdout,
+    } = redirects;
+
+    let mut command = new_command(cmd);
+
+    let stdin_bytes =
+        match stdin {
+            Stdin::Inherit => {
+                command.stdin(Stdio::inherit());
+
+                None
+            },
+            Stdin::File(path) => {
+                let f =
+                    match File::open(cmd.dir.join(&path)) {
+                        Ok(f) => f,
+                        Err(source) => {
+                            return Err(RunError::RedirectFailed{path, source});
+                        },
+                    };
+                command.stdin(f);
+
+                None
+            },
+            Stdin::Bytes(bytes) => {
+                command.stdin(Stdio::piped());
+
+                Some(bytes)
+            },
+        };
+
+    // `merged_stdout` is the reading end of the pipe that `stdout` and
+    // `stderr` are both written to, if `stderr` is merged into a captured
+    // `stdout`.
+    let mut merged_stdout = None;
+    if let Some(path) = stdout_file {
+        let f = open_output_file(&cmd.dir, path, append)?;
+        if stderr_to_stdout {
+            let f2 = f.try_clone()
+                .map_err(|source| RunError::PipeSetup{source})?;
+            command.stderr(f2);
+        }
+        command.stdout(f);
+    } else if stderr_to_stdout {
+        let (reader, writer) = io::pipe()
+            .map_err(|source| RunError::PipeSetup{source})?;
+        let writer2 = writer.try_clone()
+            .map_err(|source| RunError::PipeSetup{source})?;
+        command.stdout(writer);
+        command.stderr(writer2);
+        merged_stdout = Some(reader);
+    } else {
+        command.stdout(Stdio::piped());
+    }
+
+    if let Some(path) = stderr_file {
+        let f = open_output_file(&cmd.dir, path, append)?;
+        command.stderr(f);
+    } else if !stderr_to_stdout {
+        command.stderr(Stdio::piped());
+    }
+
+    let mut child = command.spawn()
+        .map_err(|source| RunError::SpawnFailed{source})?;
+
+    // We drop `command` so that the parent process no longer holds the writing
+    // ends of any pipes passed to the child, so that the reading ends are
+    // closed when the child exits.
+    drop(command);
+
+    // We write to `stdin` in a separate thread so that the process doesn't
+    // block on writing to a full `stdout` pipe while we're writing to its
+    // `stdin`.
+    let stdin_writer = stdin_bytes.map(|bytes| {
+        let mut child_stdin = child.stdin.take()
+            .expect("`stdin` should be piped");
+
+        thread::spawn(move || {
+            // We ignore write errors, which can occur if the process exits
+            // without reading all of its input.
+            let _ = child_stdin.write_all(&bytes);
+        })
+    });
+
+    let merged = merged_stdout.map(|mut reader| {
+        let mut buf = vec![];
+        // We ignore read errors and use whatever output was read.
+        let _ = reader.read_to_end(&mut buf);
+
+        buf
+    });
+
+    let output = child.wait_with_output()
+        .map_err(|source| RunError::SpawnFailed{source})?;
+
+    if let Some(writer) = stdin_writer {
+        writer.join()
+            .expect("`stdin` writer shouldn't panic");
+    }
+
+    Ok(Output{
+        stdout: merged.unwrap_or(output.stdout),
+        stderr: output.stderr,
+        exit_code: output.status.code(),
+    })
+}
+
+fn open_output_file(dir: &Path, path: PathBuf, append: bool)
+    -> Result<File, RunError>
+{
+    let mut opts = OpenOptions::new();
+    if append {
+        opts.append(true);
+    } else {
+        opts.write(true).truncate(true);
+    }
+
+    match opts.create(true).open(dir.join(&path)) {
+        Ok(f) => Ok(f),
+        Err(source) => Err(RunError::RedirectFailed{path, source}),
+    }
+}
+
+// `new_command` creates a `Command` that runs the program named by the first
+// item of `cmd.argv` in the environment defined by `cmd.env`, and in the
+// directory defined by `cmd.dir`.
+fn new_command(cmd: &Cmd) -> Command {
+    let (prog, args) =
+        match cmd.argv.split_first() {
+            Some(v) => v,
+            None => panic!("`argv` shouldn't be empty"),
+        };
+
+    let mut command = Command::new(to_os_string(prog));
+    command
+        .args(args.iter().map(to_os_string))
+        .current_dir(&cmd.dir)
+        .env_clear()
+        .envs(cmd.env.iter().map(|(k, v)| (k, to_os_string(v))));
+
+    command
+}
+
+fn to_os_string(s: &Str) -> OsString {
+    OsString::from_vec(s.clone())
+}
+
+// `render_argv` renders `argv` for use in error messages.
+pub fn render_argv(argv: &[Str]) -> String {
+    argv.iter()
+        .map(|arg| String::from_utf8_lossy(arg).to_string())
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+// `render_pipeline` renders `cmds` for use in error messages.
+pub fn render_pipeline(cmds: &[Cmd]) -> String {
+    cmds.iter()
+        .map(|cmd| render_argv(&cmd.argv))
+        .collect::<Vec<String>>()
+        .join(" | ")
+}
+
+// `render_run_error` renders an error from running `argv` for use in error
+// messages.
+pub fn render_run_error(argv: &[Str], e: &RunError) -> String {
+    match e {
+        RunError::RedirectFailed{path, source} =>
+            format!(
+                "couldn't open '{}' for `{}`: {}",
+                path.display(),
+                render_argv(argv),
+                source,
+            ),
+        RunError::PipeSetup{source} =>
+            format!(
+                "couldn't set up the streams of `{}`: {}",
+                render_argv(argv),
+                source,
+            ),
+        RunError::SpawnFailed{source} =>
+            format!("couldn't run `{}`: {}", render_argv(argv), source),
+    }
+}
+
+// `render_failure` returns a message describing the failure of `cmd_line` if
+// `exit_code` doesn't indicate success. The message includes `stderr` so that
+// the cause of the failure is reported along with it.
+pub fn render_failure(cmd_line: &str, exit_code: Option<i32>, stderr: &Str)
+    -> Option<String>
+{
+    let mut msg =
+        match exit_code {
+            Some(0) => return None,
+            Some(n) => format!("`{cmd_line}` exited with code {n}"),
+            None => format!("`{cmd_line}` was terminated by a signal"),
+        };
+
+    let rendered_stderr = String::from_utf8_lossy(stderr);
+    let trimmed_stderr = rendered_stderr.trim_end();
+    if !trimmed_stderr.is_empty() {
+        msg += &format!(": {trimmed_stderr}");
+    }
+
+    Some(msg)
+}
+
+pub fn new_output_object(output: Output) -> SourcedValue {
+    let props = BTreeMap::from([
+        ("stdout".to_string(), value::new_str(output.stdout)),
+        ("stderr".to_string(), value::new_str(output.stderr)),
+        ("exit_code".to_string(), new_exit_code(output.exit_code)),
+    ]);
+
+    value::new_object(props, &Mutability::Const)
+}
+
+// `PipelineOutput` is the result of running a pipeline of external commands
+// to completion.
+pub struct PipelineOutput {
+    pub stdout: Str,
+    // `stderr` contains the concatenated `stderr` output of each stage of the
+    // pipeline, in the order that the stages were defined.
+    pub stderr: Str,
+    pub exit_codes: Vec<Option<i32>>,
+}
+
+impl PipelineOutput {
+    // `exit_code` returns the exit code of the last stage of the pipeline, or,
+    // if `pipefail` is set, the exit code of the last stage that didn't
+    // succeed.
+    pub fn exit_code(&self, pipefail: bool) -> Option<i32> {
+        let last = *self.exit_codes.last()
+            .expect("pipelines shouldn't be empty");
+
+        if !pipefail {
+            return last;
+        }
+
+        for exit_code in self.exit_codes.iter().rev() {
+            if *exit_code != Some(0) {
+                return *exit_code;
+            }
+        }
+
+        last
+    }
+}
+
+// `run_pipeline` runs each command in `cmds` concurrently, connecting the
+// `stdout` of each process directly to the `stdin` of the next process. If a
+// command can't be started then the index of the command is returned with the
+// error.
+pub fn run_pipeline(cmds: &[Cmd])
+    -> Result<PipelineOutput, (usize, IoError)>
+{
+    let mut children: Vec<Child> = vec![];
+    let mut stderr_readers = vec![];
+    let mut next_stdin = Stdio::inherit();
+
+    for (i, cmd) in cmds.iter().enumerate() {
+        let maybe_child =
+            new_command(cmd)
+                .stdin(next_stdin)
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped())
+                .spawn();
+
+        let mut child =
+            match maybe_child {
+                Ok(child) => {
+                    child
+                },
+                Err(e) => {
+                    wait_all(children);
+
+                    return Err((i, e));
+                },
+            };
+
+        // We read the `stderr` of each process in a separate thread so that
+        // a process doesn't block on writing to a full `stderr` pipe while we
+        // wait on a different process.
+        let mut stderr = child.stderr.take()
+            .expect("`stderr` should be piped");
+        stderr_readers.push(thread::spawn(move || {
+            let mut buf = vec![];
+            // We ignore read errors and use whatever output was read.
+            let _ = stderr.read_to_end(&mut buf);
+
+            buf
+        }));
+
+        if i+1 < cmds.len() {
+            let stdout = child.stdout.take()
+                .expect("`stdout` should be piped");
+            next_stdin = Stdio::from(stdout);
+        } else {
+            next_stdin = Stdio::inherit();
+        }
+
+        children.push(child);
+    }
+
+    let mut stdout = vec![];
+    if let Some(child) = children.last_mut() {
+        if let Some(mut out) = child.stdout.take() {
+            // We ignore read errors and use whatever output was read.
+            let _ = out.read_to_end(&mut stdout);
+        }
+    }
+
+    let exit_codes = wait_all(children);
+
+    let mut stderr = vec![];
+    for reader in stderr_readers {
+        let buf = reader.join()
+            .expect("`stderr` reader shouldn't panic");
+        stderr.extend(buf);
+    }
+
+    Ok(PipelineOutput{stdout, stderr, exit_codes})
+}
+
+fn wait_all(children: Vec<Child>) -> Vec<Option<i32>> {
+    children
+        .into_iter()
+        .map(|mut child| {
+            match child.wait() {
+                Ok(status) => status.code(),
+                Err(_) => None,
+            }
+        })
+        .collect()
+}
+
+pub fn new_pipeline_output_object(output: &PipelineOutput, pipefail: bool)
+    -> SourcedValue
+{
+    let exit_codes =
+        output.exit_codes
+            .iter()
+            .map(|exit_code| new_exit_code(*exit_code))
+            .collect();
+
+    let props = BTreeMap::from([
+        ("stdout".to_string(), value::new_str(output.stdout.clone())),
+        ("stderr".to_string(), value::new_str(output.stderr.clone())),
+        ("exit_code".to_string(), new_exit_code(output.exit_code(pipefail))),
+        (
+            "pipestatus".to_string(),
+            value::new_list(exit_codes, &Mutability::Const),
+        ),
+    ]);
+
+    value::new_object(props, &Mutability::Const)
+}
+
+fn new_exit_code(exit_code: Option<i32>) -> SourcedValue {
+    match exit_code {
+        Some(n) => value::new_int(i64::from(n)),
+        None => value::new_null(),
+    }
+}
@@ -76,6 +76,21 @@ impl ScopeStack {
         None
     }
 
+    // `cur_scope_bindings` returns the names and values of the variables
+    // declared in the current scope.
+    pub fn cur_scope_bindings(&self) -> Vec<(String, SourcedValue)> {
+        let cur_scope =
+            self.0.last()
+                .expect("`ScopeStack` stack shouldn't be empty")
+                .try_lock()
+                .unwrap();
+
+        cur_scope
+            .iter()
+            .map(|(name, (v, _, _))| (name.clone(), v.clone()))
+            .collect()
+    }
+
     // `assign` replaces `name` in the topmost scope of this `ScopeStack`, or
     // returns an error if `name` wasn't found in this `ScopeStack`, or if
     // `name` refers to a variable defined as a constant.
@@ -3,10 +3,13 @@
 // licence that can be found in the LICENCE file.
 
 use std::collections::BTreeMap;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::Mutex;
 
 use crate::ast::Block;
+use crate::ast::EnumVariant;
+use crate::eval::EvaluationContext;
 use crate::eval::Expr;
 use crate::eval::Result;
 use super::scope::Mutability;
@@ -58,6 +61,7 @@ pub enum Value {
 
     Bool(bool),
     Int(i64),
+    Float(f64),
     Str(Str),
 
     List{items: ListRef, is_mutable: bool},
@@ -65,6 +69,14 @@ pub enum Value {
 
     BuiltinFunc{name: String, f: BuiltinFunc},
     Func(Arc<Mutex<Func>>),
+
+    // `Enum` is the type declared by an `enum` statement, whose variants are
+    // accessed as properties. A variant with a payload is accessed as an
+    // `EnumConstructor`, which is called to create a `Variant`; other
+    // variants are accessed as `Variant`s directly.
+    Enum(Arc<EnumType>),
+    EnumConstructor{typ: Arc<EnumType>, variant: usize},
+    Variant{typ: Arc<EnumType>, variant: usize, payload: List},
 }
 
 pub type Str = Vec<u8>;
@@ -80,8 +92,24 @@ pub type ObjectRef = Arc<Mutex<Object>>;
 // "output" tests.
 pub type Object = BTreeMap<String, SourcedValue>;
 
+// `EnumType` is compared by reference, so values of two `enum` declarations
+// are never equal, even if the declarations have the same name.
+#[derive(Debug)]
+pub struct EnumType {
+    pub name: String,
+    pub variants: Vec<EnumVariant>,
+}
+
+// `BuiltinFunc` is passed the location of the call to the function, so that
+// calls that it makes to other functions can be reported at that location.
 pub type BuiltinFunc =
-    fn(Option<SourcedValue>, Vec<SourcedValue>) -> Result<SourcedValue>;
+    fn(
+        &EvaluationContext,
+        (usize, usize),
+        Option<SourcedValue>,
+        Vec<SourcedValue>,
+    )
+        -> Result<SourcedValue>;
 
 #[derive(Clone, Debug)]
 pub struct Func {
@@ -90,6 +118,11 @@ pub struct Func {
     pub collect_args: bool,
     pub stmts: Block,
     pub closure: ScopeStack,
+    // `script_dir` and `script_path` are the `cur_script_dir` and
+    // `cur_script_path` of the script or module that the function was defined
+    // in, which are used when the function is called.
+    pub script_dir: PathBuf,
+    pub script_path: PathBuf,
 }
 
 pub fn new_null() -> SourcedValue {
@@ -104,6 +137,15 @@ pub fn new_int(n: i64) -> SourcedValue {
     new_val_ref_with_no_source(Value::Int(n))
 }
 
+pub fn new_float(n: f64) -> SourcedValue {
+    new_val_ref_with_no_source(Value::Float(n))
+}
+
+// `MAX_STR_LEN` is the length, in bytes, of the longest string that can be
+// created by repeating or padding a string, so that large lengths result in
+// errors instead of failed allocations.
+pub const MAX_STR_LEN: usize = 1 << 30;
+
 pub fn new_str(s: Str) -> SourcedValue {
     new_val_ref_with_no_source(Value::Str(s))
 }
@@ -119,6 +161,16 @@ pub fn new_list(list: List, mutability: &Mutability) -> SourcedValue {
     })
 }
 
+// `new_list_with_mutability` is like `new_list`, but takes the mutability of
+// the list as a flag, so that lists derived from other lists can have the same
+// mutability.
+pub fn new_list_with_mutability(list: List, is_mutable: bool) -> SourcedValue {
+    new_val_ref_with_no_source(Value::List{
+        items: Arc::new(Mutex::new(list)),
+        is_mutable,
+    })
+}
+
 pub fn new_object(object: Object, mutability: &Mutability) -> SourcedValue {
     new_val_ref_with_no_source(Value::Object{
         props: Arc::new(Mutex::new(object)),
@@ -127,6 +179,7 @@ pub fn new_object(object: Object, mutability: &Mutability) -> SourcedValue {
 }
 
 pub fn new_func(
+    context: &EvaluationContext,
     name: Option<String>,
     args: Vec<Expr>,
     collect_args: bool,
@@ -142,10 +195,34 @@ pub fn new_func(
             collect_args,
             stmts,
             closure,
+            script_dir: context.cur_script_dir.clone(),
+            script_path: context.cur_script_path.clone(),
         }))),
     )
 }
 
+pub fn new_variant(typ: &Arc<EnumType>, variant: usize, payload: List)
+    -> SourcedValue
+{
+    new_val_ref_with_no_source(Value::Variant{
+        typ: typ.clone(),
+        variant,
+        payload,
+    })
+}
+
+pub fn variant_index(typ: &EnumType, name: &str) -> Option<usize> {
+    typ.variants.iter().position(|v| v.name.0 == name)
+}
+
+// `variant_name` returns the name of the `variant` of `typ`, qualified with
+// the name of `typ`.
+pub fn variant_name(typ: &EnumType, variant: usize) -> String {
+    let (name, _) = &typ.variants[variant].name;
+
+    format!("{}.{name}", typ.name)
+}
+
 pub fn new_built_in_func(name: String, f: BuiltinFunc) -> SourcedValue {
     new_val_ref_with_no_source(Value::BuiltinFunc{name, f})
 }
@@ -0,0 +1,159 @@
+// Copyright 2026 Sean Kelleher. All rights reserved.
+// Use of this source code is governed by an MIT
+// licence that can be found in the LICENCE file.
+
+use crate::ast::Align;
+use crate::ast::FormatSpec;
+
+// `split` splits the directive of an interpolation slot into its expression
+// and its format spec, if it has one. The format spec follows the last `:`
+// that isn't nested in brackets or a string literal, and that isn't part of a
+// `::`. Whitespace around the format spec is ignored.
+pub fn split(directive: &str) -> (&str, Option<&str>) {
+    let chars: Vec<(usize, char)> = directive.char_indices().collect();
+
+    let mut depth = 0;
+    let mut in_str = false;
+    let mut escaped = false;
+    let mut sep = None;
+    for (i, &(offset, c)) in chars.iter().enumerate() {
+        if in_str {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == '"' {
+                in_str = false;
+            }
+            continue;
+        }
+
+        match c {
+            '"' => {
+                in_str = true;
+            },
+            '(' | '[' | '{' => {
+                depth += 1;
+            },
+            ')' | ']' | '}' => {
+                depth -= 1;
+            },
+            ':' if depth == 0 => {
+                let is_colon = |j: Option<usize>| {
+                    j.and_then(|j| chars.get(j)).is_some_and(|&(_, c)| {
+                        c == ':'
+                    })
+                };
+
+                if !is_colon(i.checked_sub(1)) && !is_colon(Some(i+1)) {
+                    sep = Some(offset);
+                }
+            },
+            _ => {},
+        }
+    }
+
+    match sep {
+        Some(i) => (&directive[..i], Some(directive[i+1..].trim())),
+        None => (directive, None),
+    }
+}
+
+// `parse` returns the format spec that `spec` represents, which is either
+// `json`, or of the form `[[fill]align][0][width]`, where `align` is one of
+// `<`, `>` or `^`, and `width` is at most `MAX_WIDTH`.
+pub fn parse(spec: &str) -> Option<FormatSpec> {
+    if spec == "json" {
+        return Some(FormatSpec::Json);
+    }
+
+    let chars: Vec<char> = spec.chars().collect();
+
+    let (fill, align, rest) =
+        match chars[..] {
+            [fill, a, ..] if new_align(a).is_some() =>
+                (fill, new_align(a), &chars[2..]),
+            [a, ..] if new_align(a).is_some() =>
+                (' ', new_align(a), &chars[1..]),
+            _ =>
+                (' ', None, &chars[..]),
+        };
+
+    let zero = align.is_none() && rest.len() > 1 && rest[0] == '0';
+
+    let width_chars = if zero { &rest[1..] } else { rest };
+    let is_width = width_chars.iter().all(char::is_ascii_digit);
+    if width_chars.is_empty() || !is_width {
+        return None;
+    }
+
+    let width = width_chars.iter().collect::<String>().parse().ok()?;
+    if width > MAX_WIDTH {
+        return None;
+    }
+
+    Some(FormatSpec::Pad{fill, align, zero, width})
+}
+
+// `MAX_WIDTH` is the largest width that a format spec can pad a value to, so
+// that large widths are reported as errors instead of failed allocations.
+const MAX_WIDTH: usize = 1 << 16;
+
+fn new_align(c: char) -> Option<Align> {
+    match c {
+        '<' => Some(Align::Left),
+        '>' => Some(Align::Right),
+        '^' => Some(Align::Center),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_splits() {
+        let tests = &[
+            ("n", ("n", None)),
+            ("n:05", ("n", Some("05"))),
+            ("a::len():>3", ("a::len()", Some(">3"))),
+            ("xs[1:2]", ("xs[1:2]", None)),
+            ("{\"a\": 1}:json", ("{\"a\": 1}", Some("json"))),
+            ("\":\" + s", ("\":\" + s", None)),
+            (" n : 05 ", (" n ", Some("05"))),
+            (" {\"k\": 1}:json ", (" {\"k\": 1}", Some("json"))),
+        ];
+
+        for (directive, exp) in tests {
+            assert_eq!(*exp, split(directive), "directive: '{directive}'");
+        }
+    }
+
+    #[test]
+    fn test_parses() {
+        let pad = |fill, align, zero, width| {
+            Some(FormatSpec::Pad{fill, align, zero, width})
+        };
+
+        let tests = &[
+            ("json", Some(FormatSpec::Json)),
+            ("5", pad(' ', None, false, 5)),
+            ("05", pad(' ', None, true, 5)),
+            ("0", pad(' ', None, false, 0)),
+            (">10", pad(' ', Some(Align::Right), false, 10)),
+            ("*^7", pad('*', Some(Align::Center), false, 7)),
+            ("<", None),
+            ("", None),
+            ("x", None),
+            ("5x", None),
+            ("65536", pad(' ', None, false, 65536)),
+            ("65537", None),
+            ("99999999999", None),
+        ];
+
+        for (spec, exp) in tests {
+            assert_eq!(*exp, parse(spec), "spec: '{spec}'");
+        }
+    }
+}
@@ -4,37 +4,57 @@
 
 use std::num::IntErrorKind;
 
+use crate::ast::FormatSpec;
+
+pub mod format_spec;
 mod scanner;
 
 use self::scanner::Scanner;
 
-pub type InterpSlot = (usize, usize);
+// `InterpSlot` is an interpolation slot of an interpolated string literal.
+// `start` and `end` are the byte indices of the slot, including its
+// delimiters, in the string, and `expr_loc` is the location of `expr_src` in
+// the source.
+#[derive(Clone, Debug, PartialEq)]
+pub struct InterpSlot {
+    pub start: usize,
+    pub end: usize,
+    pub expr_src: String,
+    pub expr_loc: Location,
+    pub format_spec: Option<FormatSpec>,
+}
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum Token {
     Ident(String),
     IntLiteral(i64),
+    FloatLiteral(f64),
     StrLiteral(String),
     InterpStrLiteral(String, Vec<InterpSlot>),
 
     Break,
     Continue,
     Else,
+    Enum,
     False,
     Fn,
     For,
     If,
+    Import,
     In,
+    Match,
     Null,
     Return,
     True,
     While,
 
+    Amp,
     Bang,
     BraceClose,
     BraceOpen,
     BracketClose,
     BracketOpen,
+    Caret,
     Colon,
     Comma,
     Div,
@@ -46,25 +66,36 @@ pub enum Token {
     Mul,
     ParenClose,
     ParenOpen,
+    Pipe,
     Question,
     StmtEnd,
     Sub,
     Sum,
+    Tilde,
 
     AmpAmp,
+    AmpEquals,
     BangEquals,
+    CaretEquals,
     ColonColon,
     ColonEquals,
     DashGreaterThan,
     DivEquals,
     DollarBraceOpen,
     DollarBracketOpen,
+    DollarParenOpen,
     DotDot,
     EqualsEquals,
+    EqualsGreaterThan,
     GreaterThanEquals,
+    GreaterThanGreaterThan,
     LessThanEquals,
+    LessThanLessThan,
     ModEquals,
     MulEquals,
+    MulMul,
+    PipeEquals,
+    PipeGreaterThan,
     PipePipe,
     SubEquals,
     SumEquals,
@@ -72,14 +103,20 @@ pub enum Token {
     DollarColonEquals,
     EqualsEqualsEquals,
     BangEqualsEquals,
+    GreaterThanGreaterThanEquals,
+    LessThanLessThanEquals,
+    MulMulEquals,
 }
 
 #[derive(Debug)]
 pub enum LexError {
     Unexpected(Location, char),
     IntOverflow(Location, String),
+    FloatOverflow(Location, String),
     UnescapedDollar(Location),
     InvalidInterpolationStart(Location, char),
+    EmptyInterpolationSlot(Location),
+    InvalidFormatSpec(Location, String),
     InvalidEscapeChar(Location, char),
     InvalidHexChar(Location, char),
 }
@@ -98,6 +135,15 @@ impl<'input> Lexer<'input> {
         }
     }
 
+    // `new_at` returns a `Lexer` for `chars`, where the first character of
+    // `chars` is at `start`.
+    pub fn new_at(chars: &'input str, start: Location) -> Self {
+        Lexer{
+            scanner: Scanner::new_at(chars, start),
+            last_token: None,
+        }
+    }
+
     fn skip_whitespace_and_comments(&mut self) {
         while let Some(c) = self.scanner.peek_char() {
             if c == '#' {
@@ -135,11 +181,14 @@ impl<'input> Lexer<'input> {
             "break" => Token::Break,
             "continue" => Token::Continue,
             "else" => Token::Else,
+            "enum" => Token::Enum,
             "false" => Token::False,
             "fn" => Token::Fn,
             "for" => Token::For,
             "if" => Token::If,
+            "import" => Token::Import,
             "in" => Token::In,
+            "match" => Token::Match,
             "null" => Token::Null,
             "return" => Token::Return,
             "true" => Token::True,
@@ -149,19 +198,49 @@ impl<'input> Lexer<'input> {
         }
     }
 
-    fn next_int(&mut self) -> Result<Token, LexError> {
+    // `next_number` returns an int literal, or a float literal if the digits
+    // are followed by a fraction (e.g. `1.5`) or an exponent (e.g. `1e-3`).
+    // The `.` of a fraction must be followed by a digit, so that `1..3` is
+    // lexed as a range.
+    fn next_number(&mut self) -> Result<Token, LexError> {
         let loc = self.scanner.loc();
 
         let start = self.scanner.index;
-        while let Some(c) = self.scanner.peek_char() {
-            if !c.is_ascii_digit() && c != '_' {
-                break;
-            }
+        self.skip_digits();
+
+        let mut is_float = false;
+
+        let is_digit = |c: Option<char>| c.is_some_and(|c| c.is_ascii_digit());
+        if self.scanner.peek_char() == Some('.') &&
+                is_digit(self.scanner.peek_nth_char(1)) {
+            is_float = true;
             self.scanner.next_char();
+            self.skip_digits();
+        }
+
+        if let Some('e' | 'E') = self.scanner.peek_char() {
+            let exp_len =
+                match self.scanner.peek_nth_char(1) {
+                    Some('+' | '-') => 2,
+                    _ => 1,
+                };
+            if is_digit(self.scanner.peek_nth_char(exp_len)) {
+                is_float = true;
+                for _ in 0..exp_len {
+                    self.scanner.next_char();
+                }
+                self.skip_digits();
+            }
         }
+
         let end = self.scanner.index;
 
-        let raw_int = self.scanner.range(start, end).to_string();
+        let raw_num = self.scanner.range(start, end).to_string();
+        if is_float {
+            return Self::new_float(loc, raw_num);
+        }
+
+        let raw_int = raw_num;
         let int: i64 =
             match raw_int.replace('_', "").parse() {
                 Ok(v) => {
@@ -183,6 +262,33 @@ impl<'input> Lexer<'input> {
         Ok(Token::IntLiteral(int))
     }
 
+    fn new_float(loc: Location, raw_float: String) -> Result<Token, LexError> {
+        let float: f64 =
+            match raw_float.replace('_', "").parse() {
+                Ok(v) => {
+                    v
+                },
+                Err(e) => {
+                    panic!("unexpected parse error ({e:?}) for '{raw_float}'");
+                },
+            };
+
+        if float.is_infinite() {
+            return Err(LexError::FloatOverflow(loc, raw_float));
+        }
+
+        Ok(Token::FloatLiteral(float))
+    }
+
+    fn skip_digits(&mut self) {
+        while let Some(c) = self.scanner.peek_char() {
+            if !c.is_ascii_digit() && c != '_' {
+                break;
+            }
+            self.scanner.next_char();
+        }
+    }
+
     #[allow(clippy::too_many_lines)]
     fn next_str_literal(&mut self, interpolate: bool)
         -> Result<Token, LexError>
@@ -194,6 +300,7 @@ impl<'input> Lexer<'input> {
         let mut first_hex_char = None;
 
         let mut cur_interpolation_start = 0;
+        let mut cur_interpolation_loc = (0, 0);
         let mut interpolation_slots = vec![];
         let mut interpolation_brace_count = 0;
 
@@ -209,6 +316,7 @@ impl<'input> Lexer<'input> {
                     } else if c == '$' {
                         if interpolate {
                             cur_interpolation_start = chars.len();
+                            cur_interpolation_loc = cur_loc;
                             state = StrScanState::Interpolate;
                             chars.push('$');
                         } else {
@@ -279,14 +387,17 @@ impl<'input> Lexer<'input> {
                         interpolation_brace_count -= 1;
                     }
 
+                    chars.push(c);
+
                     if interpolation_brace_count == 0 {
-                        // We shorten the slot to ignore the delimiters.
-                        let slot = (cur_interpolation_start, chars.len()+1);
+                        let slot = new_interp_slot(
+                            &chars,
+                            cur_interpolation_start,
+                            cur_interpolation_loc,
+                        )?;
                         interpolation_slots.push(slot);
                         state = StrScanState::None;
                     }
-
-                    chars.push(c);
                 },
             }
         }
@@ -412,7 +523,7 @@ impl<'input> Lexer<'input> {
             } else if c.is_ascii_alphabetic() || c == '_' {
                 self.next_keyword_or_ident()
             } else if c.is_ascii_digit() {
-                match self.next_int() {
+                match self.next_number() {
                     Ok(n) => n,
                     Err(e) => return Some(Err(e)),
                 }
@@ -492,10 +603,14 @@ impl Iterator for Lexer<'_> {
 
             if let Some(t) = last_token {
                 match t {
+                    Token::Amp |
                     Token::AmpAmp |
+                    Token::AmpEquals |
                     Token::BangEquals |
                     Token::BraceOpen |
                     Token::BracketOpen |
+                    Token::Caret |
+                    Token::CaretEquals |
                     Token::ColonEquals |
                     Token::Comma |
                     Token::Div |
@@ -503,24 +618,36 @@ impl Iterator for Lexer<'_> {
                     Token::DollarBraceOpen |
                     Token::DollarBracketOpen |
                     Token::DollarColonEquals |
+                    Token::DollarParenOpen |
                     Token::Dot |
                     Token::Equals |
                     Token::EqualsEquals |
+                    Token::EqualsGreaterThan |
                     Token::GreaterThan |
                     Token::GreaterThanEquals |
+                    Token::GreaterThanGreaterThan |
+                    Token::GreaterThanGreaterThanEquals |
                     Token::LessThan |
                     Token::LessThanEquals |
+                    Token::LessThanLessThan |
+                    Token::LessThanLessThanEquals |
                     Token::Mod |
                     Token::ModEquals |
                     Token::Mul |
                     Token::MulEquals |
+                    Token::MulMul |
+                    Token::MulMulEquals |
                     Token::ParenOpen |
+                    Token::Pipe |
+                    Token::PipeEquals |
+                    Token::PipeGreaterThan |
                     Token::PipePipe |
                     Token::StmtEnd |
                     Token::Sub |
                     Token::SubEquals |
                     Token::Sum |
-                    Token::SumEquals => {},
+                    Token::SumEquals |
+                    Token::Tilde => {},
                     _ => {
                         return Some(Ok(span));
                     },
@@ -530,13 +657,74 @@ impl Iterator for Lexer<'_> {
     }
 }
 
+// `new_interp_slot` returns the interpolation slot at the end of `chars`,
+// which starts at `chars[start]`, where `loc` is the location of the start of
+// the slot in the source.
+fn new_interp_slot(chars: &[char], start: usize, loc: Location)
+    -> Result<InterpSlot, LexError>
+{
+    // We shorten the slot to skip the delimiters (`${` at the start and `}`
+    // at the end).
+    let directive: String = chars[start+2 .. chars.len()-1].iter().collect();
+
+    let (expr_src, spec_src) = format_spec::split(&directive);
+
+    if expr_src.trim().is_empty() {
+        return Err(LexError::EmptyInterpolationSlot(loc));
+    }
+
+    // The expression of the slot starts after the `${`.
+    let expr_loc = (loc.0, loc.1 + 2);
+
+    let mut format_spec = None;
+    if let Some(spec_src) = spec_src {
+        format_spec = format_spec::parse(spec_src);
+        if format_spec.is_none() {
+            let spec_loc = advance_loc(expr_loc, &format!("{expr_src}:"));
+
+            return Err(LexError::InvalidFormatSpec(
+                spec_loc,
+                spec_src.to_string(),
+            ));
+        }
+    }
+
+    let byte_index = |i| chars[..i].iter().map(|c| c.len_utf8()).sum();
+
+    Ok(InterpSlot{
+        start: byte_index(start),
+        end: byte_index(chars.len()),
+        expr_src: expr_src.to_string(),
+        expr_loc,
+        format_spec,
+    })
+}
+
+// `advance_loc` returns the location that follows `s`, where `s` starts at
+// `loc`.
+fn advance_loc(loc: Location, s: &str) -> Location {
+    let (mut line, mut col) = loc;
+    for c in s.chars() {
+        if c == '\n' {
+            line += 1;
+            col = 1;
+        } else {
+            col += 1;
+        }
+    }
+
+    (line, col)
+}
+
 fn match_single_symbol_token(c: char) -> Option<Token> {
     match c {
+        '&' => Some(Token::Amp),
         '!' => Some(Token::Bang),
         '}' => Some(Token::BraceClose),
         '{' => Some(Token::BraceOpen),
         ']' => Some(Token::BracketClose),
         '[' => Some(Token::BracketOpen),
+        '^' => Some(Token::Caret),
         ':' => Some(Token::Colon),
         ',' => Some(Token::Comma),
         '/' => Some(Token::Div),
@@ -548,9 +736,11 @@ fn match_single_symbol_token(c: char) -> Option<Token> {
         '*' => Some(Token::Mul),
         ')' => Some(Token::ParenClose),
         '(' => Some(Token::ParenOpen),
+        '|' => Some(Token::Pipe),
         '?' => Some(Token::Question),
         '-' => Some(Token::Sub),
         '+' => Some(Token::Sum),
+        '~' => Some(Token::Tilde),
 
         _ => None,
     }
@@ -559,10 +749,13 @@ fn match_single_symbol_token(c: char) -> Option<Token> {
 fn match_double_symbol_token(a: char, b: char) -> Option<Token> {
     match (a, b) {
         ('!', '=') => Some(Token::BangEquals),
+        ('$', '(') => Some(Token::DollarParenOpen),
         ('$', '[') => Some(Token::DollarBracketOpen),
         ('$', '{') => Some(Token::DollarBraceOpen),
         ('%', '=') => Some(Token::ModEquals),
         ('&', '&') => Some(Token::AmpAmp),
+        ('&', '=') => Some(Token::AmpEquals),
+        ('*', '*') => Some(Token::MulMul),
         ('*', '=') => Some(Token::MulEquals),
         ('+', '=') => Some(Token::SumEquals),
         ('-', '=') => Some(Token::SubEquals),
@@ -571,9 +764,15 @@ fn match_double_symbol_token(a: char, b: char) -> Option<Token> {
         ('/', '=') => Some(Token::DivEquals),
         (':', ':') => Some(Token::ColonColon),
         (':', '=') => Some(Token::ColonEquals),
+        ('<', '<') => Some(Token::LessThanLessThan),
         ('<', '=') => Some(Token::LessThanEquals),
         ('=', '=') => Some(Token::EqualsEquals),
+        ('=', '>') => Some(Token::EqualsGreaterThan),
         ('>', '=') => Some(Token::GreaterThanEquals),
+        ('>', '>') => Some(Token::GreaterThanGreaterThan),
+        ('^', '=') => Some(Token::CaretEquals),
+        ('|', '=') => Some(Token::PipeEquals),
+        ('|', '>') => Some(Token::PipeGreaterThan),
         ('|', '|') => Some(Token::PipePipe),
 
         _ => None,
@@ -585,6 +784,9 @@ fn match_triple_symbol_token(a: char, b: char, c: char) -> Option<Token> {
         ('$', ':', '=') => Some(Token::DollarColonEquals),
         ('=', '=', '=') => Some(Token::EqualsEqualsEquals),
         ('!', '=', '=') => Some(Token::BangEqualsEquals),
+        ('*', '*', '=') => Some(Token::MulMulEquals),
+        ('<', '<', '=') => Some(Token::LessThanLessThanEquals),
+        ('>', '>', '=') => Some(Token::GreaterThanGreaterThanEquals),
 
         _ => None,
     }
@@ -623,6 +825,20 @@ mod test {
                     Token::StmtEnd,
                 ],
             ),
+            (
+                r#"1.5 1..2 1e3 2.5E-1 3e"#,
+                r#"(-) -()- (-) (----) --"#,
+                vec![
+                    Token::FloatLiteral(1.5),
+                    Token::IntLiteral(1),
+                    Token::DotDot,
+                    Token::IntLiteral(2),
+                    Token::FloatLiteral(1000.0),
+                    Token::FloatLiteral(0.25),
+                    Token::IntLiteral(3),
+                    Token::Ident("e".to_string()),
+                ],
+            ),
             (
                 r#"null || === fn"#,
                 r#"(--) () (-) ()"#,
@@ -633,6 +849,57 @@ mod test {
                     Token::Fn,
                 ],
             ),
+            (
+                r#"a&&b & ~c ** <<= >>"#,
+                r#"-()- - -- () (-) ()"#,
+                vec![
+                    Token::Ident("a".to_string()),
+                    Token::AmpAmp,
+                    Token::Ident("b".to_string()),
+                    Token::Amp,
+                    Token::Tilde,
+                    Token::Ident("c".to_string()),
+                    Token::MulMul,
+                    Token::LessThanLessThanEquals,
+                    Token::GreaterThanGreaterThan,
+                ],
+            ),
+            (
+                r#"a |> b|c ||d"#,
+                r#"- () --- ()-"#,
+                vec![
+                    Token::Ident("a".to_string()),
+                    Token::PipeGreaterThan,
+                    Token::Ident("b".to_string()),
+                    Token::Pipe,
+                    Token::Ident("c".to_string()),
+                    Token::PipePipe,
+                    Token::Ident("d".to_string()),
+                ],
+            ),
+            (
+                r#"$"é ${n:05}""#,
+                r#"(----------)"#,
+                vec![
+                    Token::InterpStrLiteral(
+                        "é ${n:05}".to_string(),
+                        vec![
+                            InterpSlot{
+                                start: 3,
+                                end: 10,
+                                expr_src: "n".to_string(),
+                                expr_loc: (1, 7),
+                                format_spec: Some(FormatSpec::Pad{
+                                    fill: ' ',
+                                    align: None,
+                                    zero: true,
+                                    width: 5,
+                                }),
+                            },
+                        ],
+                    ),
+                ],
+            ),
         ];
 
         for (src, encoded_exp_locs, exp_toks) in tests {
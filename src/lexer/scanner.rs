@@ -18,12 +18,18 @@ pub struct Scanner<'a> {
 
 impl<'a> Scanner<'a> {
     pub fn new(chars: &'a str) -> Self {
+        Self::new_at(chars, (1, 1))
+    }
+
+    // `new_at` returns a `Scanner` for `chars`, where the first character of
+    // `chars` is at `start`.
+    pub fn new_at(chars: &'a str, start: (usize, usize)) -> Self {
         let mut char_indices = chars.char_indices();
 
         let cur_char = char_indices.next().map(|(_, c)| c);
-        let mut loc = (1, 1);
+        let mut loc = start;
         if let Some('\n') = cur_char {
-            loc = (2, 0);
+            loc = (start.0 + 1, 0);
         }
 
         Scanner{
@@ -41,6 +47,16 @@ impl<'a> Scanner<'a> {
         self.cur_char
     }
 
+    // `peek_nth_char` returns the character that is `n` characters after the
+    // current character, so `peek_nth_char(0)` is equivalent to `peek_char()`.
+    pub fn peek_nth_char(&self, n: usize) -> Option<char> {
+        if n == 0 {
+            return self.cur_char;
+        }
+
+        self.chars.clone().nth(n - 1).map(|(_, c)| c)
+    }
+
     pub fn next_char(&mut self) {
         // We use `chars.next()` to iterate through the characters of `chars`
         // because the characters of a UTF-8 string can't be indexed in
@@ -6,12 +6,15 @@
 extern crate assert_matches;
 extern crate snafu;
 
-use std::collections::BTreeMap;
 use std::env;
+use std::ffi::OsString;
 use std::fs;
+use std::io;
 use std::io::Error as IoError;
+use std::io::Read;
 use std::path::Path;
 use std::path::PathBuf;
+use std::os::unix::ffi::OsStringExt;
 use std::process;
 use std::sync::Arc;
 use std::sync::Mutex;
@@ -27,12 +30,17 @@ use snafu::Snafu;
 
 use ast::RawExpr;
 use builtins::fns;
+use builtins::stdlib;
 use builtins::type_functions;
 use eval::builtins::Builtins;
 use eval::EvaluationContext;
 use eval::error::Error as EvalError;
+use eval::import::Modules;
 use eval::value;
-use eval::scope::ScopeStack;
+use eval::scope::Mutability;
+use eval::value::ObjectRef;
+use eval::value::Str;
+use eval::value::Value;
 use lexer::Lexer;
 use lexer::LexError;
 use lexer::Token;
@@ -49,46 +57,93 @@ lalrpop_mod!(
     parser
 );
 
+const USAGE: &str = "\
+usage: ash [<script-path> | -c <code> | -] [<args>...]
+       ash (--help | --version)
+
+Runs the Ash script at <script-path>. Any <args> that follow are passed to the
+script as the `args` list.
+
+Options:
+  -c <code>   Run <code> as the script
+  -           Read the script from standard input
+  -h, --help  Print this message and exit
+  --version   Print the version of ash and exit";
+
 fn main() {
-    let mut args = std::env::args();
+    let mut args = env::args_os();
     let prog =
         match args.next() {
-            Some(v) => v,
+            Some(v) => v.to_string_lossy().to_string(),
             None => {
                 eprintln!("couldn't get program name");
                 process::exit(101);
             },
         };
 
-    let raw_cur_rel_script_path =
-        match args.next() {
-            Some(v) => v,
-            None => {
-                eprintln!("usage: {prog} <script-path>");
+    let (script, script_args) =
+        match parse_args(args) {
+            Ok(v) => {
+                v
+            },
+            Err(CliError::Help) => {
+                println!("{USAGE}");
+                process::exit(0);
+            },
+            Err(CliError::Version) => {
+                println!("ash {}", env!("CARGO_PKG_VERSION"));
+                process::exit(0);
+            },
+            Err(CliError::Usage{msg}) => {
+                eprintln!("{prog}: {msg}; see '{prog} --help' for usage");
                 process::exit(102);
             },
         };
 
-    let cur_rel_script_path = Path::new(&raw_cur_rel_script_path);
+    let script_name = script.name();
+
+    if let Err(e) = run(&script, script_args) {
+        if let Error::EvalFailed{source} = &e {
+            if let Some(code) = eval::exit_code(source) {
+                process::exit(code);
+            }
+        }
 
-    if let Err(e) = run(cur_rel_script_path) {
-        let msg =
+        let (msg, code) =
             match e {
                 Error::GetCurrentDirFailed{source} => {
-                    format!(" couldn't get current directory: {source}")
+                    let msg = format!(
+                        "{script_name}: couldn't get current directory: \
+                         {source}",
+                    );
+
+                    (msg, 103)
                 },
                 Error::ReadScriptFailed{path, source} => {
                     let p = path.to_string_lossy();
+                    let msg = format!(
+                        "{script_name}: couldn't read script at '{p}': \
+                         {source}",
+                    );
+
+                    (msg, 103)
+                },
+                Error::ReadStdinFailed{source} => {
+                    let msg = format!(
+                        "{script_name}: couldn't read script from stdin: \
+                         {source}",
+                    );
 
-                    format!(" couldn't read script at '{p}': {source}")
+                    (msg, 103)
                 },
                 Error::ParseFailed{src} => {
                     let ((ln, ch), msg) = render_parse_error(src);
 
-                    format!("{ln}:{ch}: {msg}")
+                    (format!("{script_name}:{ln}:{ch}: {msg}"), 104)
                 },
-                Error::EvalFailed{source, path} => {
-                    let st = eval_err_to_stacktrace(&path, None, source);
+                Error::EvalFailed{source} => {
+                    let path = Path::new(&script_name);
+                    let st = eval_err_to_stacktrace(path, None, source);
 
                     let mut rendered_stacktrace = String::new();
                     if !st.stacktrace.is_empty() {
@@ -98,31 +153,182 @@ fn main() {
                         );
                     }
 
-                    format!("{}{}", st.msg, rendered_stacktrace)
+                    // Errors that occur in imported modules are reported
+                    // against the path of the module.
+                    let err_path =
+                        match st.path {
+                            Some(p) => p.to_string_lossy().to_string(),
+                            None => script_name,
+                        };
+
+                    let msg =
+                        format!("{err_path}:{}{rendered_stacktrace}", st.msg);
+
+                    (msg, 103)
                 },
             };
-        eprintln!("{raw_cur_rel_script_path}:{msg}");
-        process::exit(103);
+        eprintln!("{msg}");
+        process::exit(code);
+    }
+}
+
+// `Script` is the source of the script to run.
+enum Script {
+    File(PathBuf),
+    Inline(String),
+    Stdin,
+}
+
+impl Script {
+    // `name` returns the name that is used to refer to the script in error
+    // messages.
+    fn name(&self) -> String {
+        match self {
+            Script::File(path) => path.to_string_lossy().to_string(),
+            Script::Inline(_) => "<inline>".to_string(),
+            Script::Stdin => "<stdin>".to_string(),
+        }
     }
 }
 
-fn run(cur_rel_script_path: &Path) -> Result<(), Error> {
-    let cur_script_dir = env::current_dir()
+enum CliError {
+    Help,
+    Version,
+    Usage{msg: String},
+}
+
+// `parse_args` parses the command-line arguments that follow the program name
+// into the script to run and the arguments to pass to it.
+fn parse_args(mut args: impl Iterator<Item=OsString>)
+    -> Result<(Script, Vec<Str>), CliError>
+{
+    let arg =
+        match args.next() {
+            Some(v) => v,
+            None => return Err(CliError::Usage{
+                msg: "no script provided".to_string(),
+            }),
+        };
+
+    let script =
+        match arg.to_str() {
+            Some("-h" | "--help") => {
+                return Err(CliError::Help);
+            },
+            Some("--version") => {
+                return Err(CliError::Version);
+            },
+            Some("-c") => {
+                let code =
+                    match args.next().map(OsString::into_string) {
+                        Some(Ok(v)) => v,
+                        Some(Err(_)) => return Err(CliError::Usage{
+                            msg: "`-c` code must be valid UTF-8".to_string(),
+                        }),
+                        None => return Err(CliError::Usage{
+                            msg: "`-c` requires an argument".to_string(),
+                        }),
+                    };
+
+                Script::Inline(code)
+            },
+            Some("-") => {
+                Script::Stdin
+            },
+            Some(opt) if opt.starts_with('-') => {
+                return Err(CliError::Usage{
+                    msg: format!("unknown option '{opt}'"),
+                });
+            },
+            _ => {
+                Script::File(PathBuf::from(arg))
+            },
+        };
+
+    let script_args = args.map(OsString::into_vec).collect();
+
+    Ok((script, script_args))
+}
+
+#[allow(clippy::too_many_lines)]
+fn run(script: &Script, script_args: Vec<Str>) -> Result<(), Error> {
+    let cur_dir = env::current_dir()
         .context(GetCurrentDirFailed)?;
-    let mut cur_script_path = cur_script_dir.clone();
-    cur_script_path.push(cur_rel_script_path);
 
-    let src = fs::read_to_string(&cur_script_path)
-        .context(ReadScriptFailed{path: cur_script_path.clone()})?;
+    let (src, cur_script_path) =
+        match script {
+            Script::File(cur_rel_script_path) => {
+                let mut cur_script_path = cur_dir.clone();
+                cur_script_path.push(cur_rel_script_path);
+
+                let src = fs::read_to_string(&cur_script_path)
+                    .context(ReadScriptFailed{path: cur_script_path.clone()})?;
+
+                (src, Some(cur_script_path))
+            },
+            Script::Inline(code) => {
+                // We terminate the code with a newline so that the final
+                // statement of the code is terminated, as it would be in most
+                // script files.
+                (format!("{code}\n"), None)
+            },
+            Script::Stdin => {
+                let mut buf = String::new();
+                io::stdin().read_to_string(&mut buf)
+                    .context(ReadStdinFailed)?;
+
+                (buf, None)
+            },
+        };
+
+    // Imports in scripts that aren't read from files are resolved relative to
+    // the current directory.
+    let cur_script_dir =
+        match cur_script_path.as_ref().and_then(|p| p.parent()) {
+            Some(dir) => cur_dir.join(dir),
+            None => cur_dir.clone(),
+        };
+
+    let main_script_path =
+        cur_script_path.and_then(|p| p.canonicalize().ok());
+
+    let env_vars = new_env_vars();
+    let script_arg_vals =
+        script_args
+            .into_iter()
+            .map(value::new_str)
+            .collect();
 
     let global_bindings = vec![
         (
             RawExpr::Var{name: "print".to_string()},
             value::new_built_in_func("print".to_string(), fns::print),
         ),
+        (
+            RawExpr::Var{name: "exit".to_string()},
+            value::new_built_in_func("exit".to_string(), fns::exit),
+        ),
+        (
+            RawExpr::Var{name: "cd".to_string()},
+            value::new_built_in_func("cd".to_string(), fns::cd),
+        ),
+        (
+            RawExpr::Var{name: "with_dir".to_string()},
+            value::new_built_in_func("with_dir".to_string(), fns::with_dir),
+        ),
+        (
+            RawExpr::Var{name: "args".to_string()},
+            value::new_list(script_arg_vals, &Mutability::Const),
+        ),
+        (
+            RawExpr::Var{name: "env".to_string()},
+            value::new_val_ref_with_no_source(Value::Object{
+                props: env_vars.clone(),
+                is_mutable: true,
+            }),
+        ),
     ];
 
-    let mut scopes = ScopeStack::new(vec![]);
     let lexer = Lexer::new(&src);
     let ast =
         match ProgParser::new().parse(lexer) {
@@ -137,30 +343,54 @@ fn run(cur_rel_script_path: &Path) -> Result<(), Error> {
     eval::eval_prog(
         &EvaluationContext{
             builtins: &Builtins{
-                std: Arc::new(Mutex::new(BTreeMap::new())),
+                std: stdlib::stdlib(),
                 type_functions: type_functions::type_functions(),
             },
+            global_bindings: &global_bindings,
+            env: env_vars,
+            modules: &Mutex::new(Modules::new(
+                cur_dir.clone(),
+                main_script_path,
+            )),
             cur_script_dir,
+            cur_script_path: PathBuf::from(script.name()),
+            cwd: &Mutex::new(cur_dir),
+            pipefail: &Mutex::new(false),
         },
-        &mut scopes,
-        global_bindings.clone(),
         &ast,
     )
-        .context(EvalFailed{path: cur_rel_script_path})?;
+        .context(EvalFailed)?;
 
     Ok(())
 }
 
+// `new_env_vars` returns the environment variables of the current process.
+// Variables with names that aren't valid UTF-8 are skipped, because they can't
+// be used as property names.
+fn new_env_vars() -> ObjectRef {
+    let vars =
+        env::vars_os()
+            .filter_map(|(k, v)| {
+                let name = k.into_string().ok()?;
+
+                Some((name, value::new_str(v.into_vec())))
+            })
+            .collect();
+
+    Arc::new(Mutex::new(vars))
+}
+
 #[derive(Debug, Snafu)]
 #[snafu(context(suffix(false)))]
 #[allow(clippy::enum_variant_names)]
 enum Error {
     GetCurrentDirFailed{source: IoError},
     ReadScriptFailed{path: PathBuf, source: IoError},
+    ReadStdinFailed{source: IoError},
     // We add `ParseError` as a `src` value rather than `source` because it
     // doesn't satisfy the error constraints required by `Snafu`.
     ParseFailed{src: ParseError<(usize, usize), Token, LexError>},
-    EvalFailed{source: EvalError, path: PathBuf},
+    EvalFailed{source: EvalError},
 }
 
 fn render_parse_error(error: ParseError<(usize, usize), Token, LexError>)
@@ -195,6 +425,8 @@ fn render_parse_error(error: ParseError<(usize, usize), Token, LexError>)
                     (loc, format!("unexpected '{c}'")),
                 LexError::IntOverflow(loc, raw_int) =>
                     (loc, format!("'{raw_int}' is too high for an int")),
+                LexError::FloatOverflow(loc, raw_float) =>
+                    (loc, format!("'{raw_float}' is too large for a float")),
                 LexError::InvalidEscapeChar(loc, c) =>
                     (loc, format!("'{c}' is not a valid escape character")),
                 LexError::InvalidHexChar(loc, c) =>
@@ -208,6 +440,10 @@ fn render_parse_error(error: ParseError<(usize, usize), Token, LexError>)
                             "interpolation slots start with '{{', got '{c}'",
                         ),
                     ),
+                LexError::EmptyInterpolationSlot(loc) =>
+                    (loc, "interpolation slots can't be empty".to_string()),
+                LexError::InvalidFormatSpec(loc, spec) =>
+                    (loc, format!("'{spec}' is not a valid format spec")),
             },
     }
 }
@@ -216,6 +452,7 @@ fn render_token(t: Token) -> String {
     match t {
         Token::Ident(s) => format!("`{s}`"),
         Token::IntLiteral(n) => format!("{n}"),
+        Token::FloatLiteral(n) => format!("{n:?}"),
 
         Token::StrLiteral(s)
         | Token::InterpStrLiteral(s, _) => format!("\"{s}\""),
@@ -223,21 +460,26 @@ fn render_token(t: Token) -> String {
         Token::Break => "`break`".to_string(),
         Token::Continue => "`continue`".to_string(),
         Token::Else => "`else`".to_string(),
+        Token::Enum => "`enum`".to_string(),
         Token::False => "`false`".to_string(),
         Token::Fn => "`fn`".to_string(),
         Token::For => "`for`".to_string(),
         Token::If => "`if`".to_string(),
+        Token::Import => "`import`".to_string(),
         Token::In => "`in`".to_string(),
+        Token::Match => "`match`".to_string(),
         Token::Null => "`null`".to_string(),
         Token::Return => "`return`".to_string(),
         Token::True => "`true`".to_string(),
         Token::While => "`while`".to_string(),
 
+        Token::Amp => "&".to_string(),
         Token::Bang => "!".to_string(),
         Token::BraceClose => "}".to_string(),
         Token::BraceOpen => "{".to_string(),
         Token::BracketClose => "]".to_string(),
         Token::BracketOpen => "[".to_string(),
+        Token::Caret => "^".to_string(),
         Token::Colon => ":".to_string(),
         Token::Comma => ",".to_string(),
         Token::Div => "/".to_string(),
@@ -249,25 +491,36 @@ fn render_token(t: Token) -> String {
         Token::Mul => "*".to_string(),
         Token::ParenClose => ")".to_string(),
         Token::ParenOpen => "(".to_string(),
+        Token::Pipe => "|".to_string(),
         Token::StmtEnd => "stmt_end".to_string(),
         Token::Question => "?".to_string(),
         Token::Sub => "-".to_string(),
         Token::Sum => "+".to_string(),
+        Token::Tilde => "~".to_string(),
 
         Token::AmpAmp => "&&".to_string(),
+        Token::AmpEquals => "&=".to_string(),
         Token::BangEquals => "!=".to_string(),
+        Token::CaretEquals => "^=".to_string(),
         Token::ColonColon => "::".to_string(),
         Token::ColonEquals => ":=".to_string(),
         Token::DashGreaterThan => "->".to_string(),
         Token::DivEquals => "/=".to_string(),
         Token::DollarBraceOpen => "${".to_string(),
         Token::DollarBracketOpen => "$[".to_string(),
+        Token::DollarParenOpen => "$(".to_string(),
         Token::DotDot => "..".to_string(),
         Token::EqualsEquals => "==".to_string(),
+        Token::EqualsGreaterThan => "=>".to_string(),
         Token::GreaterThanEquals => ">=".to_string(),
+        Token::GreaterThanGreaterThan => ">>".to_string(),
         Token::LessThanEquals => "<=".to_string(),
+        Token::LessThanLessThan => "<<".to_string(),
         Token::ModEquals => "%=".to_string(),
         Token::MulEquals => "*=".to_string(),
+        Token::MulMul => "**".to_string(),
+        Token::PipeEquals => "|=".to_string(),
+        Token::PipeGreaterThan => "|>".to_string(),
         Token::PipePipe => "||".to_string(),
         Token::SubEquals => "-=".to_string(),
         Token::SumEquals => "+=".to_string(),
@@ -275,6 +528,9 @@ fn render_token(t: Token) -> String {
         Token::BangEqualsEquals => "!==".to_string(),
         Token::DollarColonEquals => "$:=".to_string(),
         Token::EqualsEqualsEquals => "===".to_string(),
+        Token::GreaterThanGreaterThanEquals => ">>=".to_string(),
+        Token::LessThanLessThanEquals => "<<=".to_string(),
+        Token::MulMulEquals => "**=".to_string(),
     }
 }
 
@@ -301,11 +557,11 @@ fn eval_err_to_stacktrace(path: &Path, func: Option<&str>, error: EvalError)
         EvalError::BindObjectSingleFailed{source} |
         EvalError::BindObjectPairFailed{source} |
         EvalError::BindListItemFailed{source} |
+        EvalError::BindVariantItemFailed{source} |
         EvalError::BindNextFailed{source} |
         EvalError::EvalProgFailed{source} |
         EvalError::EvalStmtsInNewScopeFailed{source} |
         EvalError::EvalStmtsWithScopeStackFailed{source} |
-        EvalError::EvalStmtsFailed{source} |
         EvalError::EvalDeclarationRhsFailed{source} |
         EvalError::DeclarationBindFailed{source} |
         EvalError::EvalAssignmentRhsFailed{source} |
@@ -314,6 +570,11 @@ fn eval_err_to_stacktrace(path: &Path, func: Option<&str>, error: EvalError)
         EvalError::EvalIfConditionFailed{source} |
         EvalError::EvalIfStatementsFailed{source} |
         EvalError::EvalElseStatementsFailed{source} |
+        EvalError::EvalMatchValueFailed{source} |
+        EvalError::CheckMatchPatternFailed{source} |
+        EvalError::BindMatchPatternFailed{source} |
+        EvalError::EvalMatchGuardFailed{source} |
+        EvalError::EvalMatchStatementsFailed{source} |
         EvalError::EvalWhileConditionFailed{source} |
         EvalError::EvalWhileStatementsFailed{source} |
         EvalError::EvalForIterFailed{source} |
@@ -321,8 +582,13 @@ fn eval_err_to_stacktrace(path: &Path, func: Option<&str>, error: EvalError)
         EvalError::EvalForStatementsFailed{source} |
         EvalError::ValidateArgsFailed{source} |
         EvalError::DeclareFunctionFailed{source} |
+        EvalError::ValidateVariantsFailed{source} |
+        EvalError::DeclareEnumFailed{source} |
         EvalError::EvalBlockFailed{source} |
         EvalError::EvalStmtFailed{source} |
+        EvalError::EvalReturnExprFailed{source} |
+        EvalError::EvalUnaryOpFailed{source} |
+        EvalError::ApplyUnaryOpFailed{source} |
         EvalError::EvalBinOpLhsFailed{source} |
         EvalError::EvalBinOpRhsFailed{source} |
         EvalError::ApplyBoolOpFailed{source} |
@@ -350,6 +616,8 @@ fn eval_err_to_stacktrace(path: &Path, func: Option<&str>, error: EvalError)
         EvalError::EvalCallArgsFailed{source} |
         EvalError::EvalCallFuncFailed{source} |
         EvalError::EvalCatchAsBoolFailed{source} |
+        EvalError::EvalCommandFailed{source} |
+        EvalError::EvalPipelineStageFailed{source} |
         EvalError::EvalExprFailed{source} |
         EvalError::EvalPropFailed{source} |
         EvalError::InterpolateStringFailed{source} |
@@ -367,6 +635,19 @@ fn eval_err_to_stacktrace(path: &Path, func: Option<&str>, error: EvalError)
             let mut st =
                 eval_err_to_stacktrace(path, Some(&next_func), *source);
             let (line, col) = call_loc;
+
+            // If `msg` already has a location then the error came from a
+            // function that was called by the built-in function, so we add
+            // the built-in function to the stacktrace instead.
+            if st.located {
+                let p = path.to_string_lossy();
+                let f = func.unwrap_or("<root>");
+
+                st.stacktrace.push(format!("{p}:{line}:{col}: in '{f}'"));
+
+                return st;
+            }
+
             let sep =
                 if let Some(f) = func {
                     format!(" in '{f}':")
@@ -375,15 +656,22 @@ fn eval_err_to_stacktrace(path: &Path, func: Option<&str>, error: EvalError)
                 };
 
             st.msg = format!("{}:{}:{} {}", line, col, sep, st.msg);
+            st.located = true;
+            st.path = Some(path.to_path_buf());
 
             st
         },
 
-        EvalError::EvalFuncCallFailed{source, func_name, call_loc} => {
+        EvalError::EvalFuncCallFailed{
+            source,
+            func_name,
+            func_path,
+            call_loc,
+        } => {
             let next_func =
                 func_name.unwrap_or_else(|| "<unnamed function>".to_string());
             let mut st =
-                eval_err_to_stacktrace(path, Some(&next_func), *source);
+                eval_err_to_stacktrace(&func_path, Some(&next_func), *source);
             let p = path.to_string_lossy();
             let (line, col) = call_loc;
             let f = func.unwrap_or("<root>");
@@ -393,6 +681,20 @@ fn eval_err_to_stacktrace(path: &Path, func: Option<&str>, error: EvalError)
             st
         },
 
+        EvalError::EvalImportFailed{source, path: module_path, import_loc} => {
+            let mut st = eval_err_to_stacktrace(&module_path, None, *source);
+            let p = path.to_string_lossy();
+            let (line, col) = import_loc;
+            let f = func.unwrap_or("<root>");
+
+            st.stacktrace.push(format!("{p}:{line}:{col}: in '{f}'"));
+            if st.path.is_none() {
+                st.path = Some(module_path);
+            }
+
+            st
+        },
+
         EvalError::AtLoc{source, line, col} => {
             let mut st = eval_err_to_stacktrace(path, func, *source);
             let sep =
@@ -403,12 +705,19 @@ fn eval_err_to_stacktrace(path: &Path, func: Option<&str>, error: EvalError)
                 };
 
             st.msg = format!("{}:{}:{} {}", line, col, sep, st.msg);
+            st.located = true;
+            st.path = Some(path.to_path_buf());
 
             st
         },
 
         _ => {
-            StacktracedErrorMsg{stacktrace: vec![], msg: format!("{error}")}
+            StacktracedErrorMsg{
+                stacktrace: vec![],
+                msg: format!("{error}"),
+                path: None,
+                located: false,
+            }
         },
     }
 }
@@ -416,4 +725,10 @@ fn eval_err_to_stacktrace(path: &Path, func: Option<&str>, error: EvalError)
 struct StacktracedErrorMsg {
     stacktrace: Vec<String>,
     msg: String,
+    // `path` is the path of the script or module that `msg` refers to, or
+    // `None` if `msg` refers to the main script.
+    path: Option<PathBuf>,
+    // `located` is `true` if `msg` has been prefixed with the location that
+    // it refers to.
+    located: bool,
 }
@@ -0,0 +1,169 @@
+// Copyright 2026 Sean Kelleher. All rights reserved.
+// Use of this source code is governed by an MIT
+// licence that can be found in the LICENCE file.
+
+use crate::assert_cmd::Command;
+
+fn ash() -> Command {
+    Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap()
+}
+
+#[test]
+fn version() {
+    ash()
+        .arg("--version")
+        .assert()
+        .code(0)
+        .stdout(format!("ash {}\n", env!("CARGO_PKG_VERSION")));
+}
+
+#[test]
+fn help() {
+    let assert =
+        ash()
+            .arg("--help")
+            .assert()
+            .code(0);
+
+    let stdout = String::from_utf8_lossy(&assert.get_output().stdout)
+        .to_string();
+
+    assert!(stdout.starts_with("usage: ash "));
+}
+
+#[test]
+fn no_script() {
+    ash()
+        .assert()
+        .code(102);
+}
+
+#[test]
+fn unknown_option() {
+    ash()
+        .arg("--unknown")
+        .assert()
+        .code(102);
+}
+
+#[test]
+fn inline() {
+    ash()
+        .args(["-c", "print(args)", "a", "b"])
+        .assert()
+        .code(0)
+        .stdout("[\n    a,\n    b,\n]\n");
+}
+
+#[test]
+fn inline_missing_code() {
+    ash()
+        .arg("-c")
+        .assert()
+        .code(102);
+}
+
+#[test]
+fn inline_error_location() {
+    ash()
+        .args(["-c", "x := 1\nx = 2"])
+        .assert()
+        .code(103)
+        .stderr("<inline>:2:1: cannot assign to immutable value 'x'\n");
+}
+
+#[test]
+fn stdin() {
+    ash()
+        .args(["-", "a"])
+        .write_stdin("print(args[0])\n")
+        .assert()
+        .code(0)
+        .stdout("a\n");
+}
+
+#[test]
+fn stdin_error_location() {
+    ash()
+        .arg("-")
+        .write_stdin("print(1, 2)\n")
+        .assert()
+        .code(103)
+        .stderr("<stdin>:1:1: `print` only takes 1 argument (got 2)\n");
+}
+
+#[test]
+fn args_immutable() {
+    ash()
+        .args(["-c", "args[0] = \"b\"", "a"])
+        .assert()
+        .code(103)
+        .stderr("<inline>:1:1: this list is not mutable\n");
+}
+
+#[test]
+fn import() {
+    ash()
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .arg("tests/cli/imports/main.ash")
+        .assert()
+        .code(0)
+        .stdout("evaluating util\nHello, world!\n42\ntrue\n");
+}
+
+#[test]
+fn import_cycle() {
+    ash()
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .arg("tests/cli/imports/cycle_a.ash")
+        .assert()
+        .code(103)
+        .stderr(
+            "tests/cli/imports/cycle_b.ash:1:6: import cycle detected: \
+             tests/cli/imports/cycle_a.ash -> tests/cli/imports/cycle_b.ash \
+             -> tests/cli/imports/cycle_a.ash\n\
+             Stacktrace:\n  \
+             tests/cli/imports/cycle_a.ash:1:6: in '<root>'\n",
+        );
+}
+
+#[test]
+fn import_error_location() {
+    ash()
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .arg("tests/cli/imports/error.ash")
+        .assert()
+        .code(103)
+        .stderr(
+            "tests/cli/imports/lib/error.ash:2:7: 'y' is not defined\n\
+             Stacktrace:\n  \
+             tests/cli/imports/error.ash:2:12: in 'load'\n  \
+             tests/cli/imports/error.ash:5:1: in '<root>'\n",
+        );
+}
+
+#[test]
+fn import_in_func() {
+    ash()
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .arg("tests/cli/imports/deferred.ash")
+        .assert()
+        .code(103)
+        .stdout("42\n")
+        .stderr(
+            "tests/cli/imports/lib/lazy.ash:6:11: in 'fail': 'undefined' is \
+             not defined\n\
+             Stacktrace:\n  \
+             tests/cli/imports/deferred.ash:5:1: in '<root>'\n",
+        );
+}
+
+#[test]
+fn import_pipefail() {
+    ash()
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .arg("tests/cli/imports/pipefail.ash")
+        .assert()
+        .code(0)
+        .stdout("0\n");
+}